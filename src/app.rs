@@ -0,0 +1,4398 @@
+#[cfg(any(feature = "favicons", feature = "previews"))]
+use crate::limiter::Limiter;
+#[cfg(feature = "previews")]
+use crate::results::{self, ResultItem};
+#[cfg(feature = "spellcheck")]
+use crate::spellcheck;
+#[cfg(feature = "symbols")]
+use crate::symbols::SymbolIndex;
+use crate::{
+    appearance,
+    components::{
+        avatar::avatar, badge::badge, modal::modal, popover::popover, tags::itag,
+        tooltip::tooltip_helper,
+    },
+    engines::{self, CustomEngine, SearchEngine},
+    error::SearchError,
+    export,
+    history::{self, group_by_day, group_by_engine, HistoryEntry, HistoryGroup, MAX_HISTORY},
+    i18n::{Lang, Strings},
+    keybindings::{Action, KeyCombo, Keymap},
+    opener::{self, OpenGuard, OpenMode},
+    pipeline::{Pipeline, QueryContext, QueryOptions},
+    preferences::{Density, Preferences},
+    query::{self, Bang},
+    storage,
+    styles::modern::{
+        self,
+        modern_widget::{Column, Element, Renderer, Row, Text},
+        palette_from_config, ModernButton, ModernColor, ModernContainer, ModernTheme, RGBColor,
+    },
+    suggest,
+};
+use iced::{
+    alignment::{Horizontal, Vertical},
+    clipboard, event, executor, keyboard, subscription, time,
+    widget::{
+        button, column, container, horizontal_rule, horizontal_space, image, mouse_area, radio,
+        row, scrollable, text, text_input, toggler, vertical_space, Container,
+    },
+    window, Alignment, Application, Command, Event, Font, Length, Subscription,
+};
+use iced_native::Pixels;
+
+#[cfg(feature = "symbols")]
+use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime},
+};
+
+use rand::thread_rng;
+use rand::Rng;
+
+pub struct Inputs {
+    pub query: String,
+    pub enabled: bool,
+}
+
+/// [`App`] is the search window, run with [`Application::run`].
+pub struct App {
+    pub theme: ModernTheme,
+    pub inputs: Inputs,
+    /// Engines the next search goes to, every engine when none is selected.
+    pub engines: HashSet<SearchEngine>,
+    /// Names of the custom engines the next search goes to, along with `engines`.
+    custom_selected: HashSet<String>,
+    pub searches: Vec<HistoryEntry>,
+    /// Only the history entries matching it are listed, see [`history::matches`].
+    history_filter: String,
+    /// Last removed history entry and its position, until it's undone or the toast goes.
+    removed: Option<(usize, HistoryEntry)>,
+    pub toast: Option<String>,
+    pub preferences: Preferences,
+    /// Set by [`App::headless`], it never launches the browser nor reads the system
+    /// theme and language.
+    headless: bool,
+    /// Urls opened while headless, recorded instead of launching the browser.
+    pub opened_urls: Vec<String>,
+    #[cfg(feature = "symbols")]
+    symbols: Arc<SymbolIndex>,
+    /// Downloaded logos of the engines without a bundled image, by engine id or by
+    /// the host of the custom ones.
+    #[cfg(feature = "favicons")]
+    favicons: HashMap<String, image::Handle>,
+    /// Network requests, waiting for a slot once [`MAX_REQUESTS`] are running.
+    #[cfg(any(feature = "favicons", feature = "previews"))]
+    requests: Limiter<Command<Message>>,
+    /// Top hits of the last search, or why they couldn't be fetched.
+    #[cfg(feature = "previews")]
+    results: ResultState,
+    /// Query and engine the results were last fetched for, fetched again on retry.
+    #[cfg(feature = "previews")]
+    results_query: Option<(String, Option<SearchEngine>)>,
+    /// The stored history is only overwritten once it was read.
+    history_loaded: bool,
+    history_version: u64,
+    /// Latest version handed to a save, waiting for [`Message::HistorySaved`].
+    flushed_version: u64,
+    saved_version: u64,
+    exiting: bool,
+    opened: OpenGuard,
+    screen: Screen,
+    /// History row showing its words as chips.
+    expanded: Option<u64>,
+    /// History row whose detail popover is open.
+    detail: Option<u64>,
+    /// History row whose context menu is open, there's only one at a time.
+    open_menu: Option<u64>,
+    /// History row picked with the arrow keys, searched again on Enter.
+    selected_history: Option<u64>,
+    /// Stop of the focus ring outside the history, see [`App::focused`].
+    focus: Option<Focus>,
+    /// When "Clear all" was first pressed, while it waits for the confirmation.
+    clear_requested: Option<Instant>,
+    /// Whether the suggestions dropdown is shown, typing opens it again.
+    suggestions_open: bool,
+    /// Searches being opened in the browser, the search button spins meanwhile.
+    opening: usize,
+    /// Frame of [`SPINNER`] shown on the search button.
+    spinner: usize,
+    /// Frames left of the shake of the input after submitting a blank query.
+    shake: usize,
+    /// Completions shown on the dropdown, refreshed once typing pauses.
+    suggested: Vec<String>,
+    /// Bumped on every keystroke, so only the latest refresh of `suggested` applies.
+    suggest_generation: u64,
+    keymap: Keymap,
+    /// Query left in the input when the app was last closed, offered to restore.
+    last_query: Option<String>,
+    /// History sections folded away when grouping by engine.
+    collapsed_groups: HashSet<Option<SearchEngine>>,
+    /// Searches opened on each engine so far, by engine id, saved along with the
+    /// preferences.
+    engine_searches: BTreeMap<String, u64>,
+    /// The window geometry is only stored once the config was read, so a config
+    /// that failed to load isn't replaced with the defaults.
+    preferences_loaded: bool,
+    /// Size and position the window was moved to, stored when closing.
+    window_size: Option<(u32, u32)>,
+    window_position: Option<(i32, i32)>,
+    /// Vertical scroll of the history list, from 0 at the top to 1 at the bottom.
+    history_scroll: f32,
+    /// Queries of a submit waiting for confirmation, since it opens too many tabs.
+    pending_tabs: Option<Vec<QueryContext>>,
+    /// Transforms applied to each submitted query, in order.
+    pipeline: Pipeline,
+    /// Query with its misspelled words fixed, offered in place of the typed one.
+    #[cfg(feature = "spellcheck")]
+    correction: Option<String>,
+    /// Language of the interface, the system one unless picked on the settings.
+    lang: Lang,
+    /// Hint of the query input, picked once so it doesn't change on every render.
+    placeholder: usize,
+    /// Images embedded for the engines, filled in as they're decoded after launch.
+    logos: HashMap<SearchEngine, image::Handle>,
+    /// Valid sites of `custom_engines` on the config file, read once it's loaded.
+    custom_engines: Vec<CustomEngine>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub enum Message {
+    OnPressing,
+    TagSelected(SearchEngine),
+    /// Selects only the engine, unlike [`Message::TagSelected`] which toggles it.
+    SelectEngine(SearchEngine),
+    ToggleFavorite(SearchEngine),
+    CycleEngine,
+    KeyPressed(KeyCombo, event::Status),
+    FocusInput,
+    Back,
+    ClearQueryOnly,
+    SelectPrevious,
+    SelectNext,
+    /// Removes the highlighted history entry, highlighting the one after it.
+    RemoveSelected,
+    /// Moves the focus ring forward, wrapping around after the last stop.
+    FocusNext,
+    FocusPrevious,
+    /// Enter pressed outside the input, it selects a focused tag and searches otherwise.
+    ActivateFocus,
+    ClearHistory,
+    /// "Clear all" pressed, it only clears when pressed again before it expires.
+    RequestClearHistory,
+    ClearRequestExpired(Instant),
+    ToggleGroup(Option<SearchEngine>),
+    HistoryScrolled(scrollable::RelativeOffset),
+    FilterHistory(String),
+    QueryChange(String),
+    NextTheme,
+    SetTheme(ModernTheme),
+    SetSearch(String),
+    TogglePin(u64),
+    RemoveSearch(u64),
+    CopyEngineUrl(SearchEngine),
+    DismissToast,
+    UndoRemove,
+    ExportTheme,
+    ExportResultsCsv,
+    ExportHistory,
+    Exported(Result<Option<PathBuf>, SearchError>),
+    #[cfg(feature = "symbols")]
+    SymbolsIndexed(Arc<SymbolIndex>),
+    #[cfg(feature = "favicons")]
+    FaviconLoaded(String, Result<image::Handle, SearchError>),
+    LogoLoaded(SearchEngine, image::Handle),
+    /// Fetches the top hits of a query, for the given engine.
+    #[cfg(feature = "previews")]
+    FetchResults(String, Option<SearchEngine>),
+    /// Top hits fetched for a query and engine, dropped once another was fetched.
+    #[cfg(feature = "previews")]
+    ResultsLoaded(
+        String,
+        Option<SearchEngine>,
+        Result<Vec<ResultItem>, SearchError>,
+    ),
+    #[cfg(feature = "previews")]
+    OpenResult(String),
+    HistoryLoaded(Result<Vec<HistoryEntry>, SearchError>),
+    LastQueryLoaded(Option<String>),
+    LastQuerySaved(Result<(), SearchError>),
+    RestoreLastQuery,
+    DismissLastQuery,
+    FlushHistory,
+    WindowResized(u32, u32),
+    WindowMoved(i32, i32),
+    ResetWindow,
+    HistorySaved(u64, Result<(), SearchError>),
+    FinalizeAndExit,
+    Exit,
+    NavigateTo(Screen),
+    ToggleSettings,
+    PreferencesChanged(Preferences),
+    CloseSuggestions,
+    RefreshSuggestions(u64),
+    PreferencesLoaded(Result<Preferences, SearchError>),
+    PreferencesSaved(Result<(), SearchError>),
+    ToggleExpand(u64),
+    OpenMenu(u64),
+    CloseMenu,
+    ShowDetail(u64),
+    HideDetail,
+    CopySearch(u64),
+    /// Copies the search url of a history entry, for the selected engine.
+    CopyLink(u64),
+    RerunSearch(u64),
+    /// Re-runs the newest search of the history, if there's any.
+    RepeatLast,
+    EditSearch(u64),
+    ConfirmOpenTabs,
+    OpenLink,
+    BrowserOpened(Result<(), SearchError>),
+    SpinnerTick,
+    ShakeTick,
+    CancelOpenTabs,
+    /// Toggles the custom engine with the given name, like [`Message::TagSelected`].
+    CustomTagSelected(String),
+}
+
+impl Message {
+    /// Whether the message comes from the user, rather than from a finished task or a
+    /// timer.
+    fn is_interaction(&self) -> bool {
+        match self {
+            Message::ClearRequestExpired(_)
+            | Message::RefreshSuggestions(_)
+            | Message::SpinnerTick
+            | Message::ShakeTick
+            | Message::BrowserOpened(_)
+            | Message::HistoryScrolled(_)
+            | Message::HistoryLoaded(_)
+            | Message::HistorySaved(..)
+            | Message::FlushHistory
+            | Message::LastQueryLoaded(_)
+            | Message::LastQuerySaved(_)
+            | Message::PreferencesLoaded(_)
+            | Message::PreferencesSaved(_)
+            | Message::Exported(_)
+            | Message::WindowResized(..)
+            | Message::WindowMoved(..) => false,
+            #[cfg(feature = "symbols")]
+            Message::SymbolsIndexed(_) => false,
+            #[cfg(feature = "favicons")]
+            Message::FaviconLoaded(..) => false,
+            Message::LogoLoaded(..) => false,
+            #[cfg(feature = "previews")]
+            Message::ResultsLoaded(..) => false,
+            _ => true,
+        }
+    }
+}
+
+/// Screen shown on the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+    Main,
+    Settings,
+}
+
+/// Stop of the focus ring walked with Tab and Shift+Tab, Enter activates it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Input,
+    SearchButton,
+    Tag(SearchEngine),
+    /// History entry, the same one highlighted with the arrow keys.
+    History(u64),
+}
+
+/// Inline results of the last search, from the fetch to what came back.
+#[cfg(feature = "previews")]
+#[derive(Debug, Clone, PartialEq)]
+enum ResultState {
+    /// Nothing searched yet.
+    Idle,
+    Loading,
+    /// The search got no hits.
+    Empty,
+    /// Why the results couldn't be fetched, worded to be shown.
+    Error(String),
+    Loaded(Vec<ResultItem>),
+}
+
+/// Time given to the last save before closing anyway.
+const SAVE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Searches shown as chips under the input.
+const RECENT_CHIPS: usize = 5;
+/// Completions offered from each source while typing.
+const MAX_SUGGESTIONS: usize = 5;
+/// Frames of the search button while searches are being opened.
+const SPINNER: [char; 3] = ['\u{F420}', '\u{F41F}', '\u{F41E}'];
+/// Frames of the shake of the input, each one [`SHAKE_FRAME`] long.
+const SHAKE_FRAMES: usize = 6;
+const SHAKE_FRAME: Duration = Duration::from_millis(50);
+/// Time given to confirm clearing the history before the button goes back.
+const CLEAR_CONFIRM_TIMEOUT: Duration = Duration::from_secs(3);
+/// Pause in the typing after which the completions are computed.
+const SUGGEST_DELAY: Duration = Duration::from_millis(150);
+/// Characters of a query shown on its chip before cutting it.
+const CHIP_LENGTH: usize = 24;
+/// Network requests running at the same time.
+#[cfg(any(feature = "favicons", feature = "previews"))]
+const MAX_REQUESTS: usize = 4;
+/// Height of the history list, the rows out of it plus [`HISTORY_BUFFER`] aren't built.
+const HISTORY_HEIGHT: f32 = 200.0;
+const HISTORY_PADDING: f32 = 20.0;
+const HISTORY_BUFFER: f32 = 120.0;
+const ROW_HEIGHT: f32 = 40.0;
+const EXPANDED_ROW_HEIGHT: f32 = 75.0;
+const GROUP_HEADER_HEIGHT: f32 = 30.0;
+
+const ICON_FONT: Font = Font::External {
+    name: "icons",
+    bytes: include_bytes!("fonts/bootstrap-icons.ttf"),
+};
+
+const BOLD_FONT: Font = Font::External {
+    name: "bold font",
+    bytes: include_bytes!("fonts/Inter-Bold.otf"),
+};
+
+impl Application for App {
+    type Executor = executor::Default;
+    type Message = Message;
+    type Theme = ModernTheme;
+    type Flags = Vec<Message>;
+
+    fn new(flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
+        App::build(flags, false)
+    }
+
+    fn title(&self) -> String {
+        "Capy search".into()
+    }
+
+    fn update(&mut self, message: Self::Message) -> iced::Command<Self::Message> {
+        // Doing anything else cancels a pending clear of the history.
+        if message.is_interaction() && !matches!(message, Message::RequestClearHistory) {
+            self.clear_requested = None;
+        }
+        match message {
+            Message::OnPressing => {
+                if let Some(id) = self.selected_history.take() {
+                    return self.update(Message::RerunSearch(id));
+                }
+                self.suggestions_open = false;
+                let opts = QueryOptions {
+                    trim: self.preferences.trim_queries,
+                    quote: self.preferences.quote_queries,
+                };
+                let (links, queries): (Vec<String>, Vec<String>) =
+                    query::split_queries(&self.inputs.query)
+                        .into_iter()
+                        .partition(|query| {
+                            self.preferences.open_urls_directly && query::is_url(query)
+                        });
+                let opened: Vec<Command<Message>> = links
+                    .iter()
+                    .map(|link| self.open_link(link.trim()))
+                    .collect();
+                let queries: Vec<QueryContext> = queries
+                    .into_iter()
+                    .map(|query| self.pipeline.run(QueryContext::new(query, None, opts)))
+                    .filter(|context| !context.query.trim().is_empty())
+                    .flat_map(|context| self.split_engines(context))
+                    .collect();
+                if queries.is_empty() {
+                    if !links.is_empty() {
+                        return Command::batch(opened);
+                    }
+                    #[cfg(feature = "sound")]
+                    if self.preferences.sound_feedback {
+                        beep();
+                    }
+                    self.shake = SHAKE_FRAMES;
+                    return Command::none();
+                }
+                if tabs(&queries) > self.preferences.confirm_tab_threshold {
+                    self.pending_tabs = Some(queries);
+                    return Command::batch(opened);
+                }
+                return Command::batch(opened.into_iter().chain([self.submit(queries)]));
+            }
+            Message::BrowserOpened(result) => {
+                self.opening = self.opening.saturating_sub(1);
+                if let Err(err) = result {
+                    self.toast = Some(
+                        self.lang
+                            .strings()
+                            .browser_failed
+                            .replace("{}", &err.to_string()),
+                    )
+                }
+            }
+            Message::SpinnerTick => self.spinner = (self.spinner + 1) % SPINNER.len(),
+            Message::ShakeTick => self.shake = self.shake.saturating_sub(1),
+            Message::OpenLink => {
+                let link = self.inputs.query.trim().to_string();
+                return self.open_link(&link);
+            }
+            Message::ConfirmOpenTabs => {
+                if let Some(queries) = self.pending_tabs.take() {
+                    return self.submit(queries);
+                }
+            }
+            Message::CancelOpenTabs => self.pending_tabs = None,
+            Message::NextTheme => return self.update(Message::SetTheme(self.theme.next())),
+            Message::SetTheme(theme) => {
+                self.theme = theme;
+                let mut preferences = self.preferences.clone();
+                preferences.theme = Some(theme);
+                return self.update(Message::PreferencesChanged(preferences));
+            }
+            Message::QueryChange(query) => {
+                let query = if self.preferences.sanitize_input {
+                    query::strip_control(&query)
+                } else {
+                    query
+                };
+                self.set_query(query);
+                self.selected_history = None;
+                // Typing puts the focus ring back on the input.
+                self.focus = Some(Focus::Input);
+                self.suggestions_open = true;
+                self.suggest_generation += 1;
+                let generation = self.suggest_generation;
+                let refresh = Command::perform(
+                    async move {
+                        tokio::time::sleep(SUGGEST_DELAY).await;
+                        generation
+                    },
+                    Message::RefreshSuggestions,
+                );
+                if self.preferences.live_clipboard {
+                    return Command::batch([refresh, clipboard::write(self.inputs.query.clone())]);
+                }
+                return refresh;
+            }
+            // Keystrokes typed since scheduling it make the refresh stale.
+            Message::RefreshSuggestions(generation) => {
+                if generation == self.suggest_generation {
+                    self.suggested = self.suggestions();
+                }
+            }
+            // Picked queries are complete, so they don't bring up suggestions.
+            Message::SetSearch(query) => {
+                self.set_query(query);
+                self.suggestions_open = false;
+            }
+            Message::CloseSuggestions => self.suggestions_open = false,
+            Message::TagSelected(engine) => {
+                if !self.engines.remove(&engine) {
+                    self.engines.insert(engine);
+                }
+                if self.preferences.clear_on_engine_switch {
+                    self.set_query(String::new());
+                }
+            }
+            Message::CustomTagSelected(name) => {
+                if !self.custom_selected.remove(&name) {
+                    self.custom_selected.insert(name);
+                }
+                if self.preferences.clear_on_engine_switch {
+                    self.set_query(String::new());
+                }
+            }
+            Message::SelectEngine(engine) => self.select_engine(Some(engine)),
+            Message::KeyPressed(combo, status) => {
+                // A focused input takes the plain keys, so they can still be typed.
+                let typing = status == event::Status::Captured && !combo.works_while_typing();
+                if typing {
+                    return Command::none();
+                }
+                if let Some(action) = self.keymap.action(&combo) {
+                    return self.update(action_message(action));
+                }
+                // Unbound digits pick the engine at that position on the tags.
+                if let Some(engine) = combo
+                    .digit()
+                    .and_then(|digit| self.preferences.engines().get(digit - 1).copied())
+                {
+                    return self.update(Message::TagSelected(engine));
+                }
+            }
+            Message::FocusInput => return self.set_focus(Focus::Input),
+            Message::ClearQueryOnly => {
+                self.set_query(String::new());
+                return text_input::focus(query_input());
+            }
+            Message::SelectPrevious | Message::SelectNext => {
+                let visible = self.visible_entries();
+                let len = visible.len();
+                if len == 0 {
+                    return Command::none();
+                }
+                let position = self
+                    .selected_history
+                    .and_then(|id| visible.iter().position(|visible| *visible == id));
+                // Moving past either end wraps around to the other one.
+                let position = match message {
+                    Message::SelectPrevious => position.map_or(len - 1, |p| (p + len - 1) % len),
+                    _ => position.map_or(0, |p| (p + 1) % len),
+                };
+                self.selected_history = Some(visible[position]);
+            }
+            Message::ActivateFocus => {
+                return match self.focused() {
+                    Some(Focus::Tag(engine)) => self.update(Message::TagSelected(engine)),
+                    _ => self.update(Message::OnPressing),
+                };
+            }
+            Message::FocusNext | Message::FocusPrevious => {
+                let ring = self.focus_ring();
+                let len = ring.len();
+                let position = self
+                    .focused()
+                    .and_then(|focused| ring.iter().position(|stop| *stop == focused));
+                let position = match message {
+                    Message::FocusPrevious => position.map_or(len - 1, |p| (p + len - 1) % len),
+                    _ => position.map_or(0, |p| (p + 1) % len),
+                };
+                return self.set_focus(ring[position]);
+            }
+            Message::RemoveSelected => {
+                let Some(id) = self.selected_history else {
+                    return Command::none();
+                };
+                let visible = self.visible_entries();
+                // The highlight moves to the next row, or the previous one past the end.
+                self.selected_history = visible
+                    .iter()
+                    .position(|visible| *visible == id)
+                    .and_then(|p| visible.get(p + 1).or(p.checked_sub(1).map(|p| &visible[p])))
+                    .copied();
+                return self.update(Message::RemoveSearch(id));
+            }
+            Message::ToggleGroup(engine) => {
+                if !self.collapsed_groups.remove(&engine) {
+                    self.collapsed_groups.insert(engine);
+                }
+            }
+            Message::HistoryScrolled(offset) => self.history_scroll = offset.y,
+            Message::FilterHistory(filter) => {
+                self.history_filter = filter;
+                self.history_scroll = 0.0;
+                return scrollable::snap_to(history_list(), scrollable::RelativeOffset::START);
+            }
+            Message::RequestClearHistory => {
+                if self.clear_requested.take().is_some() {
+                    return self.update(Message::ClearHistory);
+                }
+                let requested = Instant::now();
+                self.clear_requested = Some(requested);
+                return Command::perform(
+                    async move {
+                        tokio::time::sleep(CLEAR_CONFIRM_TIMEOUT).await;
+                        requested
+                    },
+                    Message::ClearRequestExpired,
+                );
+            }
+            Message::ClearRequestExpired(requested) => {
+                if self.clear_requested == Some(requested) {
+                    self.clear_requested = None;
+                }
+            }
+            Message::ClearHistory => {
+                self.searches.retain(|entry| entry.pinned);
+                self.removed = None;
+                self.expanded = None;
+                self.detail = None;
+                return self.history_changed();
+            }
+            Message::CycleEngine => {
+                let next = self.preferences.next_engine(self.engine());
+                self.select_engine(Some(next));
+                if self.preferences.clear_on_engine_switch {
+                    self.set_query(String::new());
+                }
+            }
+            Message::TogglePin(id) => {
+                self.open_menu = None;
+                if let Some(entry) = self.entry(id) {
+                    entry.pinned = !entry.pinned;
+                    return self.history_changed();
+                }
+            }
+            Message::RemoveSearch(id) => {
+                if let Some(position) = self.searches.iter().position(|entry| entry.id == id) {
+                    self.removed = Some((position, self.searches.remove(position)));
+                    self.toast = Some(self.lang.strings().search_removed.into());
+                    self.expanded = self.expanded.filter(|expanded| *expanded != id);
+                    self.detail = self.detail.filter(|detail| *detail != id);
+                    self.open_menu = self.open_menu.filter(|menu| *menu != id);
+                    self.selected_history =
+                        self.selected_history.filter(|selected| *selected != id);
+                    return self.history_changed();
+                }
+            }
+            Message::CopyEngineUrl(engine) => {
+                self.toast = Some(self.lang.strings().link_copied.replace("{}", engine.name()));
+                let query = query::prepare(&self.inputs.query, self.preferences.trim_queries)
+                    .unwrap_or_default();
+                return clipboard::write(engine.url(&query));
+            }
+            Message::DismissToast => {
+                self.toast = None;
+                self.removed = None;
+            }
+            Message::UndoRemove => {
+                if let Some((position, entry)) = self.removed.take() {
+                    self.searches
+                        .insert(position.min(self.searches.len()), entry);
+                    self.toast = None;
+                    return self.history_changed();
+                }
+            }
+            Message::ExportTheme => {
+                return Command::perform(
+                    save_file("theme.toml", "toml", self.theme.palette().to_toml()),
+                    Message::Exported,
+                );
+            }
+            Message::ExportResultsCsv => {
+                return Command::perform(
+                    save_file("results.csv", "csv", export::history_csv(&self.searches)),
+                    Message::Exported,
+                );
+            }
+            Message::ExportHistory => {
+                return Command::perform(
+                    save_file("history.txt", "txt", export::history_text(&self.searches)),
+                    Message::Exported,
+                );
+            }
+            Message::Exported(result) => match result {
+                Ok(Some(path)) => {
+                    let path = path.display().to_string();
+                    self.toast = Some(self.lang.strings().exported.replace("{}", &path))
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    self.toast = Some(
+                        self.lang
+                            .strings()
+                            .export_failed
+                            .replace("{}", &err.to_string()),
+                    )
+                }
+            },
+            #[cfg(feature = "symbols")]
+            Message::SymbolsIndexed(index) => self.symbols = index,
+            #[cfg(feature = "favicons")]
+            Message::FaviconLoaded(id, result) => {
+                // On errors the engine keeps its avatar.
+                if let Ok(handle) = result {
+                    self.favicons.insert(id, handle);
+                }
+                return self.request_finished();
+            }
+            Message::LogoLoaded(engine, handle) => {
+                self.logos.insert(engine, handle);
+            }
+            #[cfg(feature = "previews")]
+            Message::FetchResults(query, engine) => {
+                self.results = ResultState::Loading;
+                self.results_query = Some((query.clone(), engine));
+                let fetch = Command::perform(
+                    results::fetch_results(query.clone(), engine),
+                    move |results| Message::ResultsLoaded(query, engine, results),
+                );
+                // Only the latest search is worth fetching once the slots are busy.
+                return self.limited(fetch, true);
+            }
+            #[cfg(feature = "previews")]
+            Message::ResultsLoaded(query, engine, results) => {
+                let next = self.request_finished();
+                // A slower answer for an older search mustn't replace the latest one.
+                if self.results_query != Some((query, engine)) {
+                    return next;
+                }
+                let strings = self.lang.strings();
+                self.results = match results {
+                    Ok(items) if items.is_empty() => ResultState::Empty,
+                    Ok(items) => ResultState::Loaded(items),
+                    Err(SearchError::Network(_)) => {
+                        ResultState::Error(strings.results_offline.into())
+                    }
+                    Err(err) => {
+                        ResultState::Error(strings.results_failed.replace("{}", &err.to_string()))
+                    }
+                };
+                return next;
+            }
+            #[cfg(feature = "previews")]
+            Message::OpenResult(url) => return self.open_url(url, self.preferences.open_mode),
+            Message::HistoryLoaded(Ok(entries)) => {
+                // Searches made while loading are newer than the stored ones.
+                self.searches.extend(entries);
+                self.history_loaded = true;
+                // The limit may have been lowered since the history was saved.
+                let limit = self.preferences.history_limit;
+                if self.searches.len() > limit {
+                    history::trim(&mut self.searches, limit);
+                    return self.history_changed();
+                }
+                if self.is_dirty() {
+                    return self.history_changed();
+                }
+            }
+            Message::HistoryLoaded(Err(err)) => {
+                self.toast = Some(
+                    self.lang
+                        .strings()
+                        .history_load_failed
+                        .replace("{}", &err.to_string()),
+                )
+            }
+            Message::HistorySaved(version, Ok(())) => {
+                self.saved_version = self.saved_version.max(version)
+            }
+            Message::FlushHistory => return self.flush_history(),
+            Message::WindowResized(width, height) => self.window_size = Some((width, height)),
+            Message::WindowMoved(x, y) => self.window_position = Some((x, y)),
+            Message::ResetWindow => {
+                self.window_size = None;
+                self.window_position = None;
+                let mut preferences = self.preferences.clone();
+                preferences.window_size = None;
+                preferences.window_position = None;
+                let (width, height) = window::Settings::default().size;
+                return Command::batch([
+                    window::resize(width, height),
+                    self.update(Message::PreferencesChanged(preferences)),
+                ]);
+            }
+            Message::HistorySaved(_, Err(err)) => {
+                // The next tick tries again.
+                self.flushed_version = self.saved_version;
+                self.toast = Some(
+                    self.lang
+                        .strings()
+                        .history_save_failed
+                        .replace("{}", &err.to_string()),
+                )
+            }
+            Message::FinalizeAndExit => {
+                if self.exiting {
+                    return Command::none();
+                }
+                self.exiting = true;
+                let history = (self.history_loaded && self.is_dirty())
+                    .then(|| storage::save_history(storage::history_path(), self.searches.clone()));
+                let query = Some(self.inputs.query.trim())
+                    .filter(|query| self.preferences.restore_last_query && !query.is_empty())
+                    .map(str::to_string);
+                let last_query = storage::save_last_query(storage::last_query_path(), query);
+                let mut preferences = self.preferences.clone();
+                preferences.window_size = self.window_size.or(preferences.window_size);
+                preferences.window_position = self.window_position.or(preferences.window_position);
+                preferences.engine_searches = self.engine_searches.clone();
+                let window = (self.preferences_loaded && preferences != self.preferences)
+                    .then(|| storage::save_preferences(storage::preferences_path(), preferences));
+                let save = async move {
+                    if let Some(history) = history {
+                        let _ = history.await;
+                    }
+                    if let Some(window) = window {
+                        let _ = window.await;
+                    }
+                    let _ = last_query.await;
+                };
+                return Command::perform(
+                    async move { tokio::time::timeout(SAVE_TIMEOUT, save).await },
+                    |_| Message::Exit,
+                );
+            }
+            Message::Exit => return window::close(),
+            // Steps back one thing at a time: the settings, the open popups, the query.
+            Message::Back => {
+                let dropdown = self.suggestions_open && !self.suggested.is_empty();
+                let popup = self.detail.is_some() || self.open_menu.is_some();
+                if self.screen != Screen::Main || popup || dropdown {
+                    return self.update(Message::NavigateTo(Screen::Main));
+                }
+                self.set_query(String::new());
+            }
+            Message::NavigateTo(screen) => {
+                self.screen = screen;
+                self.detail = None;
+                self.open_menu = None;
+                self.suggestions_open = false;
+            }
+            Message::ToggleSettings => {
+                self.screen = match self.screen {
+                    Screen::Main => Screen::Settings,
+                    Screen::Settings => Screen::Main,
+                }
+            }
+            Message::ToggleFavorite(engine) => {
+                let mut preferences = self.preferences.clone();
+                preferences.toggle_favorite(engine);
+                return self.update(Message::PreferencesChanged(preferences));
+            }
+            Message::PreferencesChanged(preferences) => {
+                self.preferences = preferences;
+                self.preferences.engine_searches = self.engine_searches.clone();
+                modern::set_accent(self.preferences.accent);
+                self.lang = self
+                    .preferences
+                    .language
+                    .unwrap_or_else(|| self.system_lang());
+                // Saving before the stored preferences are loaded would overwrite them.
+                if !self.preferences_loaded {
+                    return Command::none();
+                }
+                return Command::perform(
+                    storage::save_preferences(
+                        storage::preferences_path(),
+                        self.preferences.clone(),
+                    ),
+                    Message::PreferencesSaved,
+                );
+            }
+            Message::PreferencesLoaded(Ok(preferences)) => {
+                self.preferences = preferences;
+                modern::set_accent(self.preferences.accent);
+                self.lang = self
+                    .preferences
+                    .language
+                    .unwrap_or_else(|| self.system_lang());
+                self.preferences_loaded = true;
+                self.engine_searches = self.preferences.engine_searches.clone();
+                let (keymap, warnings) = Keymap::new(&self.preferences.keybindings);
+                for warning in warnings {
+                    eprintln!("keybindings: {warning}");
+                }
+                self.keymap = keymap;
+                let (engines, warnings) = engines::custom_engines(&self.preferences.custom_engines);
+                for warning in warnings {
+                    eprintln!("custom_engines: {warning}");
+                }
+                self.custom_engines = engines;
+                // The custom theme is only offered when its file was loaded.
+                if let Some(theme) = self.preferences.theme {
+                    if ModernTheme::available().contains(&theme) {
+                        self.theme = theme;
+                    }
+                }
+                // An engine picked while loading, like from a deep link, wins.
+                if self.engines.is_empty() {
+                    let engine = self.preferences.default_engine.as_deref();
+                    self.select_engine(engine.and_then(SearchEngine::from_id));
+                }
+                #[allow(unused_mut)]
+                let mut commands = Vec::new();
+                if self.preferences.restore_last_query {
+                    commands.push(Command::perform(
+                        storage::load_last_query(storage::last_query_path()),
+                        Message::LastQueryLoaded,
+                    ));
+                }
+                #[cfg(feature = "symbols")]
+                if let Some(root) = self.preferences.symbols_root.clone() {
+                    commands.push(Command::perform(
+                        index_symbols(root),
+                        Message::SymbolsIndexed,
+                    ));
+                }
+                #[cfg(feature = "favicons")]
+                {
+                    let missing: Vec<(String, String)> = self
+                        .custom_engines
+                        .iter()
+                        .filter_map(|engine| {
+                            let host = crate::favicon::host(&engine.url)?;
+                            let missing = !self.favicons.contains_key(host);
+                            missing.then(|| (host.to_string(), engine.url.clone()))
+                        })
+                        .collect();
+                    for (id, url) in missing {
+                        commands.push(self.limited(fetch_favicon(id, url), false));
+                    }
+                }
+                return Command::batch(commands);
+            }
+            Message::LastQueryLoaded(query) => self.last_query = query,
+            Message::RestoreLastQuery => {
+                if let Some(query) = self.last_query.take() {
+                    self.set_query(query);
+                }
+                return forget_last_query();
+            }
+            Message::DismissLastQuery => {
+                self.last_query = None;
+                return forget_last_query();
+            }
+            Message::LastQuerySaved(Ok(())) => {}
+            Message::LastQuerySaved(Err(err)) => {
+                self.toast = Some(
+                    self.lang
+                        .strings()
+                        .last_query_failed
+                        .replace("{}", &err.to_string()),
+                )
+            }
+            Message::PreferencesLoaded(Err(err)) => {
+                self.toast = Some(
+                    self.lang
+                        .strings()
+                        .preferences_load_failed
+                        .replace("{}", &err.to_string()),
+                )
+            }
+            Message::PreferencesSaved(Ok(())) => {}
+            Message::PreferencesSaved(Err(err)) => {
+                self.toast = Some(
+                    self.lang
+                        .strings()
+                        .preferences_save_failed
+                        .replace("{}", &err.to_string()),
+                )
+            }
+            Message::ShowDetail(id) => self.detail = Some(id),
+            Message::HideDetail => self.detail = None,
+            Message::CopySearch(id) => {
+                self.detail = None;
+                self.open_menu = None;
+                if let Some(entry) = self.entry(id) {
+                    let query = entry.query.clone();
+                    self.toast = Some(self.lang.strings().query_copied.into());
+                    return clipboard::write(query);
+                }
+            }
+            // Without a single engine selected it falls back to the one searched, or
+            // else to the web search.
+            Message::CopyLink(id) => {
+                self.detail = None;
+                let selected = self.engine();
+                if let Some(entry) = self.entry(id).cloned() {
+                    let custom = entry
+                        .custom
+                        .as_deref()
+                        .and_then(|name| self.custom_engine(name));
+                    let (name, url) = match (selected, custom) {
+                        (None, Some(custom)) => (custom.name.as_str(), custom.url(&entry.query)),
+                        _ => {
+                            let engine = selected
+                                .or(entry.engine)
+                                .unwrap_or(SearchEngine::DuckDuckGo);
+                            (engine.name(), engine.url(&entry.query))
+                        }
+                    };
+                    self.toast = Some(self.lang.strings().link_copied.replace("{}", name));
+                    return clipboard::write(url);
+                }
+            }
+            Message::RerunSearch(id) => {
+                self.detail = None;
+                self.open_menu = None;
+                if let Some(entry) = self.entry(id) {
+                    entry.reruns += 1;
+                    let HistoryEntry {
+                        query,
+                        engine,
+                        custom,
+                        ..
+                    } = entry.clone();
+                    let opened = self.open_search(&query, engine, custom.as_deref());
+                    self.set_query(query);
+                    self.suggestions_open = false;
+                    return Command::batch([opened, self.history_changed()]);
+                }
+            }
+            Message::RepeatLast => {
+                if let Some(entry) = self.searches.first() {
+                    return self.update(Message::RerunSearch(entry.id));
+                }
+            }
+            Message::EditSearch(id) => {
+                self.detail = None;
+                if let Some(HistoryEntry {
+                    query,
+                    engine,
+                    custom,
+                    ..
+                }) = self.entry(id).cloned()
+                {
+                    self.set_query(query);
+                    self.select_engine(engine);
+                    self.custom_selected.extend(custom);
+                    return text_input::focus(query_input());
+                }
+            }
+            Message::OpenMenu(id) => {
+                self.detail = None;
+                self.open_menu = Some(id);
+            }
+            Message::CloseMenu => self.open_menu = None,
+            Message::ToggleExpand(id) => {
+                self.open_menu = None;
+                self.expanded = if self.expanded == Some(id) {
+                    None
+                } else {
+                    Some(id)
+                }
+            }
+        }
+        Command::none()
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        if self.screen == Screen::Settings {
+            return self.settings_view();
+        }
+
+        let strings = self.lang.strings();
+        let placeholder = strings.placeholders[self.placeholder];
+        let scale = self.preferences.font_scale;
+
+        let title = container(
+            column![
+                Text::new("Capy")
+                    .size(scaled(75, scale))
+                    .font(BOLD_FONT)
+                    .style(ModernColor::Custom(252.0, 187.0, 150.0)),
+                Text::new(strings.subtitle).size(scaled(18, scale))
+            ]
+            .spacing(15)
+            .align_items(Alignment::Center),
+        );
+
+        let favorites = self.preferences.favorites();
+        let engines: Vec<Element<Message>> = self
+            .preferences
+            .engines()
+            .into_iter()
+            .map(|engine| {
+                let selected = self.engines.contains(&engine);
+                let logo = self.logo(engine);
+                // Bundled logos still being decoded show a placeholder on the tag.
+                let tag: Element<Message> = if logo.is_some() || engine.image().is_some() {
+                    itag(
+                        logo,
+                        engine,
+                        self.preferences.tag_icon_size,
+                        selected,
+                        Message::TagSelected(engine),
+                        Message::CopyEngineUrl(engine),
+                    )
+                    .into()
+                } else {
+                    mouse_area(
+                        button(avatar(
+                            engine.name(),
+                            engine.color_for(self.theme),
+                            self.preferences.tag_icon_size,
+                        ))
+                        .padding(0)
+                        .style(if selected {
+                            ModernButton::SelectedTag(engine)
+                        } else {
+                            ModernButton::Text
+                        })
+                        .on_press(Message::TagSelected(engine)),
+                    )
+                    .on_middle_press(Message::CopyEngineUrl(engine))
+                    .into()
+                };
+                // A dot next to the star marks the engine the next search goes to.
+                let mut marks = row![favorite_star(engine, favorites.contains(&engine))]
+                    .spacing(4)
+                    .align_items(Alignment::Center);
+                if selected {
+                    marks = marks.push(badge(Some(engine.color_for(self.theme))));
+                }
+                let tag = focus_outline(tag, self.focused() == Some(Focus::Tag(engine)));
+                column![tooltip_helper(tag, engine.name()), marks]
+                    .spacing(2)
+                    .align_items(Alignment::Center)
+                    .into()
+            })
+            .collect();
+        // Custom engines come after the built-in ones, selected the same way.
+        let engines = engines
+            .into_iter()
+            .chain(self.custom_engines.iter().map(|engine| {
+                let size = self.preferences.tag_icon_size;
+                let logo: Option<image::Handle> = None;
+                #[cfg(feature = "favicons")]
+                let logo = logo.or_else(|| {
+                    let host = crate::favicon::host(&engine.url)?;
+                    self.favicons.get(host).cloned()
+                });
+                // The avatar stands in while the favicon loads, or when it fails.
+                let content: Element<Message> = match logo {
+                    Some(logo) => image(logo).height(size).into(),
+                    None => avatar(&engine.name, engine.color_for(self.theme), size).into(),
+                };
+                let tag = button(content)
+                    .padding(0)
+                    .style(ModernButton::Text)
+                    .on_press(Message::CustomTagSelected(engine.name.clone()));
+                let mut marks = column![tooltip_helper(tag, engine.name.as_str())]
+                    .spacing(2)
+                    .align_items(Alignment::Center);
+                if self.custom_selected.contains(&engine.name) {
+                    marks = marks.push(badge(Some(engine.color_for(self.theme))));
+                }
+                marks.into()
+            }));
+        let engines: Vec<Element<Message>> = engines.collect();
+        let spacing = self.preferences.tag_icon_size / 3;
+        // Only the engines scroll, the scrollbar shows up once they don't fit.
+        let engines = scrollable(
+            row(engines)
+                .spacing(spacing)
+                .padding([0, 0, 8, 0])
+                .align_items(Alignment::Center),
+        )
+        .horizontal_scroll(scrollable::Properties::new().width(4).scroller_width(4));
+
+        let tags = row![
+            engines,
+            button(
+                row![icon('\u{F1D2}', 16), text(self.theme.name()).size(14)]
+                    .spacing(6)
+                    .align_items(Alignment::Center),
+            )
+            .height(30)
+            .padding([6, 12])
+            .on_press(Message::NextTheme)
+            .style(ModernButton::Secondary),
+            button(icon('\u{F3E5}', 16))
+                .height(30)
+                .width(30)
+                .padding(6.2)
+                .on_press(Message::ToggleSettings)
+                .style(ModernButton::Secondary),
+        ]
+        .spacing(spacing)
+        .align_items(Alignment::Center);
+
+        let mut input = text_input(placeholder, &self.inputs.query)
+            .id(query_input())
+            .size(scaled(20, scale))
+            .padding([12, 20]);
+        // The focused input keeps the Enter key from the shortcuts, so it submits itself.
+        if self.inputs.enabled {
+            input = input
+                .on_input(Message::QueryChange)
+                .on_submit(Message::OnPressing);
+        }
+
+        let mut quoted = self.preferences.clone();
+        quoted.quote_queries = !quoted.quote_queries;
+        let quote_chip = button(text("\" \"").size(14))
+            .padding([3, 10])
+            .style(if self.preferences.quote_queries {
+                ModernButton::Principal
+            } else {
+                ModernButton::Secondary
+            })
+            .on_press(Message::PreferencesChanged(quoted));
+
+        let mut input_row = row![input].width(595).align_items(Alignment::Center);
+        // Clears the text alone, keeping the engine and the query options.
+        if !self.inputs.query.is_empty() {
+            input_row = input_row.push(
+                button(icon('\u{F62A}', 16))
+                    .padding([3, 6])
+                    .style(ModernButton::Text)
+                    .on_press(Message::ClearQueryOnly),
+            );
+        }
+        // Short queries can still be searched, they only look disabled.
+        let too_short = query::is_too_short(&self.inputs.query);
+        let search_icon = if self.is_loading() {
+            SPINNER[self.spinner]
+        } else {
+            '\u{F144}'
+        };
+        let search_button = button(icon(search_icon, 16))
+            .height(30)
+            .width(30)
+            .padding(6.2)
+            .style(if self.inputs.query.trim().is_empty() || too_short {
+                ModernButton::Secondary
+            } else {
+                ModernButton::Principal
+            })
+            .on_press(Message::OnPressing);
+        let input_row = input_row
+            .push(quote_chip)
+            .push(horizontal_space(8))
+            .push(focus_outline(
+                search_button,
+                self.focused() == Some(Focus::SearchButton),
+            ));
+
+        // A blank submit shakes the input from side to side, outlined in red meanwhile.
+        let (input_style, offset) = match self.shake {
+            0 => (ModernContainer::Input, [0, 0, 0, 0]),
+            frame if frame % 2 == 0 => (ModernContainer::InputError, [0, 0, 0, 8]),
+            _ => (ModernContainer::InputError, [0, 8, 0, 0]),
+        };
+        let input_and_button = container(input_row)
+            .width(610)
+            .padding(offset)
+            .center_x()
+            .center_y()
+            .style(input_style);
+
+        let dropdown = (self.suggestions_open && !self.suggested.is_empty())
+            .then(|| show_suggestions(self.suggested.clone()));
+        let mut input_and_button = popover(input_and_button, dropdown);
+        if self.preferences.close_suggestions_on_click_outside {
+            input_and_button = input_and_button.on_dismiss(Message::CloseSuggestions);
+        }
+
+        let mut input_block = column![input_and_button]
+            .align_items(Alignment::Center)
+            .spacing(10);
+        if too_short {
+            input_block = input_block.push(
+                text(strings.query_too_short)
+                    .size(scaled(14, scale))
+                    .style(ModernColor::Muted),
+            );
+        }
+        if let Some(engine) = self.engine() {
+            input_block = input_block.push(
+                text(engine.hint())
+                    .size(scaled(14, scale))
+                    .style(ModernColor::Muted),
+            );
+            input_block = input_block.push(operator_chips(engine, &self.inputs.query));
+        }
+        if !self.searches.is_empty() {
+            input_block = input_block.push(recent_chips(&self.searches));
+        }
+
+        let title: Element<Message> = if favorites.is_empty() {
+            title.into()
+        } else {
+            column![favorites_bar(&favorites), title]
+                .spacing(20)
+                .align_items(Alignment::Center)
+                .into()
+        };
+
+        let density = self.preferences.density.factor();
+        let mut principal_column = column![title, input_block]
+            .align_items(Alignment::Center)
+            .spacing(scaled(30, density));
+        if !self.preferences.open_urls_directly && query::is_url(&self.inputs.query) {
+            principal_column = principal_column.push(
+                button(text(strings.open_link).size(16))
+                    .padding([5, 15])
+                    .style(ModernButton::Secondary)
+                    .on_press(Message::OpenLink),
+            );
+        }
+        if let Some(Bang::Unknown(bang, rest)) = query::parse_bang(&self.inputs.query) {
+            if let Some((engine, alias)) = suggest::closest_bang(bang) {
+                principal_column = principal_column.push(
+                    button(text(strings.did_you_mean.replace("{}", &format!("!{alias}"))).size(16))
+                        .padding([5, 15])
+                        .style(ModernButton::Tag(Some(engine)))
+                        .on_press(Message::QueryChange(format!("!{alias} {rest}"))),
+                );
+            }
+        }
+        let broader = suggest::broader_engine(
+            &self.inputs.query,
+            self.engine(),
+            self.preferences.long_query_words,
+        );
+        if let Some(engine) = broader.filter(|_| self.preferences.long_query_hint) {
+            principal_column = principal_column.push(
+                button(text(strings.long_query.replace("{}", engine.name())).size(16))
+                    .padding([5, 15])
+                    .style(ModernButton::Tag(Some(engine)))
+                    .on_press(Message::TagSelected(engine)),
+            );
+        }
+        #[cfg(feature = "spellcheck")]
+        if let Some(correction) = &self.correction {
+            principal_column = principal_column.push(
+                button(text(strings.spelling.replace("{}", correction)).size(16))
+                    .padding([5, 15])
+                    .style(ModernButton::Secondary)
+                    .on_press(Message::SetSearch(correction.clone())),
+            );
+        }
+        #[cfg(feature = "previews")]
+        if let Some(results) = results_view(
+            &self.results,
+            self.results_query.as_ref(),
+            self.preferences.result_limit,
+            strings,
+        ) {
+            principal_column = principal_column.push(results);
+        }
+        let principal_container: container::Container<Message, Renderer> =
+            container(principal_column);
+
+        let historial_container = if self.searches.is_empty() {
+            empty_message(strings.empty_history, scale, density)
+        } else if !self.searches.iter().any(|entry| self.filtered(entry)) {
+            empty_message(strings.no_matches, scale, density)
+        } else {
+            self.show_historial()
+        };
+
+        let light_mode = toggler(
+            strings.light_mode.to_string(),
+            self.theme == ModernTheme::Light,
+            light_mode,
+        )
+        .size(20)
+        .text_size(16)
+        .width(Length::Shrink);
+
+        let mut content = column![light_mode, principal_container, tags, horizontal_rule(1)]
+            .align_items(Alignment::Center)
+            .spacing(scaled(15, density));
+        content = content.push(history_header(
+            self.searches.is_empty(),
+            self.clear_requested.is_some(),
+            &self.history_filter,
+            strings,
+        ));
+        if self.searches.len() >= self.preferences.history_limit {
+            content = content.push(history_limit_notice(
+                self.searches.len(),
+                &self.preferences,
+                strings,
+            ));
+        }
+        content = content.push(historial_container);
+        if !self.searches.is_empty() {
+            let shown = self.searches.iter().filter(|entry| self.filtered(entry));
+            content = content.push(history_count(
+                self.searches.len(),
+                shown.count(),
+                scale,
+                strings,
+            ));
+        }
+
+        if let Some(msg) = &self.toast {
+            let mut toast = row![toast_message(msg)]
+                .spacing(10)
+                .align_items(Alignment::Center);
+            if self.removed.is_some() {
+                toast = toast.push(
+                    button(text(strings.undo).size(14))
+                        .padding([6, 14])
+                        .style(ModernButton::Secondary)
+                        .on_press(Message::UndoRemove),
+                );
+            }
+            content = content.push(toast);
+        }
+
+        let principal_box = container(container(content))
+            .padding(10)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y();
+
+        let view = principal_box.into();
+        if let Some(queries) = &self.pending_tabs {
+            return confirm_tabs(view, tabs(queries), strings);
+        }
+        match &self.last_query {
+            Some(query) => restore_prompt(view, query, strings),
+            None => view,
+        }
+    }
+
+    fn theme(&self) -> Self::Theme {
+        self.theme
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        let events = subscription::events_with(on_event);
+        let toast = if self.toast.is_some() {
+            time::every(Duration::from_secs(3)).map(|_| Message::DismissToast)
+        } else {
+            Subscription::none()
+        };
+        // Closing still saves the changes made since the last flush.
+        let delay = self.preferences.history_save_delay_ms;
+        let flush = if self.history_loaded && self.flushed_version < self.history_version {
+            time::every(Duration::from_millis(delay.max(1))).map(|_| Message::FlushHistory)
+        } else {
+            Subscription::none()
+        };
+        let spinner = if self.is_loading() {
+            time::every(Duration::from_millis(200)).map(|_| Message::SpinnerTick)
+        } else {
+            Subscription::none()
+        };
+        let shake = if self.shake > 0 {
+            time::every(SHAKE_FRAME).map(|_| Message::ShakeTick)
+        } else {
+            Subscription::none()
+        };
+        Subscription::batch([events, toast, flush, spinner, shake])
+    }
+}
+
+/// Theme picked by the light mode toggler, the other themes toggle back to dark.
+fn light_mode(light: bool) -> Message {
+    Message::SetTheme(if light {
+        ModernTheme::Light
+    } else {
+        ModernTheme::Dark
+    })
+}
+
+/// Maps the window and keyboard events to their messages.
+fn on_event(event: Event, status: event::Status) -> Option<Message> {
+    match event {
+        Event::Window(window::Event::CloseRequested) => Some(Message::FinalizeAndExit),
+        Event::Window(window::Event::Resized { width, height }) => {
+            Some(Message::WindowResized(width, height))
+        }
+        Event::Window(window::Event::Moved { x, y }) => Some(Message::WindowMoved(x, y)),
+        Event::Keyboard(keyboard::Event::KeyPressed {
+            key_code,
+            modifiers,
+        }) => Some(Message::KeyPressed(
+            KeyCombo::new(key_code, modifiers),
+            status,
+        )),
+        _ => None,
+    }
+}
+
+/// Message that performs a shortcut `action`.
+fn action_message(action: Action) -> Message {
+    match action {
+        Action::Submit => Message::ActivateFocus,
+        Action::ToggleTheme => Message::NextTheme,
+        Action::FocusInput => Message::FocusInput,
+        Action::ClearHistory => Message::RequestClearHistory,
+        Action::OpenSettings => Message::ToggleSettings,
+        Action::Back => Message::Back,
+        Action::CycleEngine => Message::CycleEngine,
+        Action::ClearQuery => Message::ClearQueryOnly,
+        Action::SelectPrevious => Message::SelectPrevious,
+        Action::SelectNext => Message::SelectNext,
+        Action::RepeatLast => Message::RepeatLast,
+        Action::RemoveSelected => Message::RemoveSelected,
+        Action::FocusNext => Message::FocusNext,
+        Action::FocusPrevious => Message::FocusPrevious,
+    }
+}
+
+impl App {
+    /// App that never opens a window nor the browser, for driving it from tests and
+    /// benchmarks. Searches are recorded on `opened_urls`, and it keeps the dark theme
+    /// and English whatever the system prefers.
+    pub fn headless() -> Self {
+        App::build(Vec::new(), true).0
+    }
+
+    fn build(flags: Vec<Message>, headless: bool) -> (Self, Command<Message>) {
+        #[allow(unused_mut)]
+        let mut app = App {
+            theme: ModernTheme::Dark,
+            inputs: Inputs {
+                query: String::new(),
+                enabled: true,
+            },
+            engines: HashSet::new(),
+            custom_selected: HashSet::new(),
+            searches: Vec::new(),
+            history_filter: String::new(),
+            removed: None,
+            toast: None,
+            preferences: Preferences::default(),
+            headless,
+            opened_urls: Vec::new(),
+            #[cfg(feature = "symbols")]
+            symbols: Arc::default(),
+            #[cfg(feature = "favicons")]
+            favicons: HashMap::new(),
+            #[cfg(any(feature = "favicons", feature = "previews"))]
+            requests: Limiter::new(MAX_REQUESTS),
+            #[cfg(feature = "previews")]
+            results: ResultState::Idle,
+            #[cfg(feature = "previews")]
+            results_query: None,
+            history_loaded: false,
+            history_version: 0,
+            flushed_version: 0,
+            saved_version: 0,
+            exiting: false,
+            opened: OpenGuard::default(),
+            screen: Screen::Main,
+            expanded: None,
+            detail: None,
+            open_menu: None,
+            selected_history: None,
+            focus: Some(Focus::Input),
+            clear_requested: None,
+            suggestions_open: false,
+            opening: 0,
+            spinner: 0,
+            shake: 0,
+            suggested: Vec::new(),
+            suggest_generation: 0,
+            pending_tabs: None,
+            pipeline: Pipeline::default(),
+            keymap: Keymap::default(),
+            last_query: None,
+            collapsed_groups: HashSet::new(),
+            engine_searches: BTreeMap::new(),
+            history_scroll: 0.0,
+            preferences_loaded: false,
+            window_size: None,
+            window_position: None,
+            #[cfg(feature = "spellcheck")]
+            correction: None,
+            lang: Lang::En,
+            placeholder: thread_rng().gen_range(0..Lang::En.strings().placeholders.len()),
+            logos: HashMap::new(),
+            custom_engines: Vec::new(),
+        };
+        app.lang = app.system_lang();
+        // Headless apps keep the dark theme whatever the machine prefers.
+        if !headless && appearance::prefers_light() == Some(true) {
+            app.theme = ModernTheme::Light;
+        }
+        // A theme.toml next to the binary takes the place of the system one.
+        if let Some(exe) = std::env::current_exe().ok().filter(|_| !headless) {
+            match palette_from_config(&exe.with_file_name("theme.toml")) {
+                Ok(palette) => app.theme = ModernTheme::custom(palette),
+                Err(SearchError::Io(_)) => {}
+                Err(err) => {
+                    let strings = app.lang.strings();
+                    app.toast = Some(strings.theme_load_failed.replace("{}", &err.to_string()))
+                }
+            }
+        }
+
+        let mut commands = vec![
+            Command::perform(
+                storage::load_preferences(storage::preferences_path()),
+                Message::PreferencesLoaded,
+            ),
+            Command::perform(
+                storage::load_history(storage::history_path()),
+                Message::HistoryLoaded,
+            ),
+        ];
+        commands.push(text_input::focus(query_input()));
+        commands.extend(flags.into_iter().map(message));
+        commands.extend(SearchEngine::KNOWN.into_iter().filter_map(load_logo));
+        #[cfg(feature = "favicons")]
+        for engine in SearchEngine::KNOWN {
+            if engine.image().is_none() {
+                let favicon = fetch_favicon(engine.id().to_string(), engine.url(""));
+                commands.push(app.limited(favicon, false));
+            }
+        }
+
+        (app, Command::batch(commands))
+    }
+
+    /// Stops of the focus ring in order: the input, the search button, the engine
+    /// tags and the listed history entries.
+    fn focus_ring(&self) -> Vec<Focus> {
+        let mut ring = vec![Focus::Input, Focus::SearchButton];
+        ring.extend(self.preferences.engines().into_iter().map(Focus::Tag));
+        ring.extend(self.visible_entries().into_iter().map(Focus::History));
+        ring
+    }
+
+    /// Stop of the focus ring the keyboard is on, the highlighted history entry
+    /// taking over the others.
+    fn focused(&self) -> Option<Focus> {
+        self.selected_history.map(Focus::History).or(self.focus)
+    }
+
+    /// Moves the focus ring to `focus`, the input only keeps the keyboard while on it.
+    fn set_focus(&mut self, focus: Focus) -> Command<Message> {
+        self.selected_history = None;
+        self.focus = Some(focus);
+        match focus {
+            Focus::Input => return text_input::focus(query_input()),
+            Focus::History(id) => self.selected_history = Some(id),
+            Focus::SearchButton | Focus::Tag(_) => {}
+        }
+        // No input has this id, so focusing it takes the focus away from the query.
+        text_input::focus(text_input::Id::unique())
+    }
+
+    /// Ids of the history entries listed, in the order they are shown.
+    fn visible_entries(&self) -> Vec<u64> {
+        self.history_items()
+            .into_iter()
+            .filter_map(|item| match item {
+                HistoryItem::Entry(i) => Some(self.searches[i].id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Rows of the history list, in the order they are shown.
+    fn history_items(&self) -> Vec<HistoryItem> {
+        let shown = |id: &usize| self.filtered(&self.searches[*id]);
+        match self.preferences.history_group {
+            HistoryGroup::None => {
+                let ranked: Vec<usize> = history::rank(&self.searches)
+                    .into_iter()
+                    .filter(shown)
+                    .collect();
+                let pinned = ranked
+                    .iter()
+                    .take_while(|id| self.searches[**id].pinned)
+                    .count();
+                let recent = ranked.len() - pinned;
+                let mut items: Vec<HistoryItem> =
+                    ranked.into_iter().map(HistoryItem::Entry).collect();
+                if pinned > 0 {
+                    let strings = self.lang.strings();
+                    if recent > 0 {
+                        items.insert(pinned, HistoryItem::Section(strings.recent));
+                    }
+                    items.insert(0, HistoryItem::Section(strings.pinned));
+                }
+                items
+            }
+            HistoryGroup::Engine => group_by_engine(&self.searches)
+                .into_iter()
+                .map(|(engine, ids)| (engine, ids.into_iter().filter(shown).collect::<Vec<_>>()))
+                .filter(|(_, ids)| !ids.is_empty())
+                .flat_map(|(engine, ids)| {
+                    let collapsed = self.collapsed_groups.contains(&engine);
+                    let header = HistoryItem::Group(engine, ids.len(), collapsed);
+                    let rows = if collapsed { Vec::new() } else { ids };
+                    std::iter::once(header).chain(rows.into_iter().map(HistoryItem::Entry))
+                })
+                .collect(),
+            HistoryGroup::Day => group_by_day(&self.searches)
+                .into_iter()
+                .map(|(day, ids)| (day, ids.into_iter().filter(shown).collect::<Vec<_>>()))
+                .filter(|(_, ids)| !ids.is_empty())
+                .flat_map(|(day, ids)| {
+                    std::iter::once(HistoryItem::Day(day))
+                        .chain(ids.into_iter().map(HistoryItem::Entry))
+                })
+                .collect(),
+        }
+    }
+
+    /// History list, split in a collapsible section per engine when grouped.
+    ///
+    /// Only the rows around the scrolled position are built, spacers take the place
+    /// of the others so the scrollbar still reflects the whole list.
+    fn show_historial(&self) -> Container<'_, Message, Renderer> {
+        let items = self.history_items();
+        let heights: Vec<f32> = items
+            .iter()
+            .map(|item| match item {
+                HistoryItem::Group(..) | HistoryItem::Section(_) | HistoryItem::Day(_) => {
+                    GROUP_HEADER_HEIGHT
+                }
+                HistoryItem::Entry(i) if self.expanded == Some(self.searches[*i].id) => {
+                    EXPANDED_ROW_HEIGHT
+                }
+                HistoryItem::Entry(_) => ROW_HEIGHT,
+            })
+            .collect();
+
+        let density = self.preferences.density.factor();
+        let (height, padding) = (HISTORY_HEIGHT * density, HISTORY_PADDING * density);
+        let content = heights.iter().sum::<f32>() + 2.0 * padding;
+        let offset = self.history_scroll * (content - height).max(0.0) - padding;
+        let window = history::window(&heights, offset, height, HISTORY_BUFFER);
+
+        let strings = self.lang.strings();
+        let now = SystemTime::now();
+        let today = history::day(now);
+        let rows = window.rows.map(|i| {
+            let item: Element<Message> = match items[i] {
+                HistoryItem::Group(engine, count, collapsed) => {
+                    group_header(engine, count, collapsed, self.theme, strings)
+                }
+                HistoryItem::Section(title) => {
+                    text(title).size(14).style(ModernColor::Muted).into()
+                }
+                HistoryItem::Day(day) => text(history::day_label(day, today, strings))
+                    .size(14)
+                    .style(ModernColor::Muted)
+                    .into(),
+                HistoryItem::Entry(position) => {
+                    let entry = &self.searches[position];
+                    let expanded = self.expanded == Some(entry.id);
+                    let row = historial_text(
+                        entry,
+                        &self.history_filter,
+                        now,
+                        self.theme,
+                        self.preferences.compact_rows,
+                        expanded,
+                        self.detail == Some(entry.id),
+                        self.preferences.font_scale,
+                        strings,
+                    );
+                    let menu = self.open_menu == Some(entry.id);
+                    popover(row, menu.then(|| history_menu(entry, expanded, strings)))
+                        .at_cursor()
+                        .on_dismiss(Message::CloseMenu)
+                        .into()
+                }
+            };
+            let selected = matches!(items[i], HistoryItem::Entry(position)
+                if self.selected_history == Some(self.searches[position].id));
+            let row = container(item).height(heights[i]).center_y();
+            if selected {
+                row.style(ModernContainer::Highlight).into()
+            } else {
+                row.into()
+            }
+        });
+        let data: Vec<Element<Message>> = std::iter::once(vertical_space(window.before).into())
+            .chain(rows)
+            .chain(std::iter::once(vertical_space(window.after).into()))
+            .collect();
+
+        container(
+            scrollable(
+                column(data)
+                    .padding([padding as u16, 30])
+                    .align_items(Alignment::Start),
+            )
+            .id(history_list())
+            .on_scroll(Message::HistoryScrolled)
+            .width(580),
+        )
+        .width(610)
+        .height(height)
+        .style(ModernContainer::Historial)
+    }
+
+    fn settings_view(&self) -> Element<'_, Message> {
+        let strings = self.lang.strings();
+        let title = Text::new(strings.settings).size(40).font(BOLD_FONT);
+
+        let options = column![
+            preference(
+                strings.trim_queries,
+                &self.preferences,
+                self.preferences.trim_queries,
+                |preferences, value| preferences.trim_queries = value,
+            ),
+            preference(
+                strings.sanitize_input,
+                &self.preferences,
+                self.preferences.sanitize_input,
+                |preferences, value| preferences.sanitize_input = value,
+            ),
+            preference(
+                strings.clear_on_engine_switch,
+                &self.preferences,
+                self.preferences.clear_on_engine_switch,
+                |preferences, value| preferences.clear_on_engine_switch = value,
+            ),
+            preference(
+                strings.clear_after_search,
+                &self.preferences,
+                self.preferences.clear_after_search,
+                |preferences, value| preferences.clear_after_search = value,
+            ),
+            preference(
+                strings.dedupe_opens,
+                &self.preferences,
+                self.preferences.dedupe_opens,
+                |preferences, value| preferences.dedupe_opens = value,
+            ),
+            preference(
+                strings.background_open,
+                &self.preferences,
+                self.preferences.background_open,
+                |preferences, value| preferences.background_open = value,
+            ),
+            preference(
+                strings.open_urls_directly,
+                &self.preferences,
+                self.preferences.open_urls_directly,
+                |preferences, value| preferences.open_urls_directly = value,
+            ),
+            preference(
+                strings.restore_last_query,
+                &self.preferences,
+                self.preferences.restore_last_query,
+                |preferences, value| preferences.restore_last_query = value,
+            ),
+            preference(
+                strings.compact_rows,
+                &self.preferences,
+                self.preferences.compact_rows,
+                |preferences, value| preferences.compact_rows = value,
+            ),
+            preference(
+                strings.close_suggestions,
+                &self.preferences,
+                self.preferences.close_suggestions_on_click_outside,
+                |preferences, value| preferences.close_suggestions_on_click_outside = value,
+            ),
+            preference(
+                strings.long_query_hint,
+                &self.preferences,
+                self.preferences.long_query_hint,
+                |preferences, value| preferences.long_query_hint = value,
+            ),
+            column![
+                preference(
+                    strings.live_clipboard,
+                    &self.preferences,
+                    self.preferences.live_clipboard,
+                    |preferences, value| preferences.live_clipboard = value,
+                ),
+                text(strings.live_clipboard_note)
+                    .size(13)
+                    .style(ModernColor::Muted),
+            ]
+            .spacing(4),
+        ]
+        .spacing(15);
+        #[cfg(feature = "sound")]
+        let options = options.push(preference(
+            strings.sound_feedback,
+            &self.preferences,
+            self.preferences.sound_feedback,
+            |preferences, value| preferences.sound_feedback = value,
+        ));
+
+        let engines = self.preferences.engines();
+        let order = Column::with_children(
+            engines
+                .iter()
+                .enumerate()
+                .map(|(position, engine)| {
+                    engine_order_row(
+                        &self.preferences,
+                        *engine,
+                        self.theme,
+                        position > 0,
+                        position + 1 < engines.len(),
+                    )
+                })
+                .collect(),
+        )
+        .spacing(5);
+        let order = column![text(strings.engine_order).size(16), order].spacing(10);
+
+        let preferences = &self.preferences;
+        let tag_size = |label: &str, tag_icon_size| {
+            let updated = Preferences {
+                tag_icon_size,
+                ..preferences.clone()
+            };
+            choice(label, preferences.tag_icon_size == tag_icon_size, updated)
+        };
+        let tag_sizes = row![
+            text(strings.tag_size).size(16).width(Length::Fill),
+            tag_size(strings.small, 24),
+            tag_size(strings.medium, 30),
+            tag_size(strings.large, 40),
+        ]
+        .spacing(15)
+        .align_items(Alignment::Center);
+
+        let themes = row![text(strings.theme).size(16).width(Length::Fill)]
+            .spacing(15)
+            .align_items(Alignment::Center);
+        let themes = ModernTheme::available()
+            .into_iter()
+            .fold(themes, |themes, theme| {
+                themes.push(
+                    radio(theme.name(), theme, Some(self.theme), Message::SetTheme)
+                        .size(16)
+                        .text_size(14),
+                )
+            });
+
+        let accent = |label: &str, accent: Option<RGBColor>| {
+            let updated = Preferences {
+                accent,
+                ..preferences.clone()
+            };
+            choice(label, preferences.accent == accent, updated)
+        };
+        let accents = row![
+            text(strings.accent).size(16).width(Length::Fill),
+            accent(strings.theme_accent, None),
+            accent(strings.blue, Some((51.0, 136.0, 255.0))),
+            accent(strings.green, Some((46.0, 164.0, 79.0))),
+            accent(strings.pink, Some((219.0, 68.0, 130.0))),
+            accent(strings.purple, Some((137.0, 87.0, 229.0))),
+        ]
+        .spacing(15)
+        .align_items(Alignment::Center);
+
+        let default_engine = |label: &str, engine: Option<SearchEngine>| {
+            let default_engine = engine.map(|engine| engine.id().to_string());
+            let selected = preferences.default_engine == default_engine;
+            let updated = Preferences {
+                default_engine,
+                ..preferences.clone()
+            };
+            choice(label, selected, updated)
+        };
+        let default_engines = SearchEngine::KNOWN.into_iter().fold(
+            row![
+                text(strings.default_engine).size(16).width(Length::Fill),
+                default_engine(strings.all, None),
+            ],
+            |row, engine| row.push(default_engine(engine.name(), Some(engine))),
+        );
+        let default_engines = default_engines.spacing(15).align_items(Alignment::Center);
+
+        let language = |label: &str, language: Option<Lang>| {
+            let updated = Preferences {
+                language,
+                ..preferences.clone()
+            };
+            choice(label, preferences.language == language, updated)
+        };
+        let languages = Lang::ALL.into_iter().fold(
+            row![
+                text(strings.language).size(16).width(Length::Fill),
+                language(strings.system, None),
+            ],
+            |row, lang| row.push(language(lang.name(), Some(lang))),
+        );
+        let languages = languages.spacing(15).align_items(Alignment::Center);
+
+        let font_scale = |label: &str, font_scale: f32| {
+            let updated = Preferences {
+                font_scale,
+                ..preferences.clone()
+            };
+            choice(label, preferences.font_scale == font_scale, updated)
+        };
+        let font_scales = row![
+            text(strings.text_size).size(16).width(Length::Fill),
+            font_scale("100%", 1.0),
+            font_scale("125%", 1.25),
+            font_scale("150%", 1.5),
+        ]
+        .spacing(15)
+        .align_items(Alignment::Center);
+
+        let open_mode = |label: &str, open_mode: OpenMode| {
+            let updated = Preferences {
+                open_mode,
+                ..preferences.clone()
+            };
+            choice(label, preferences.open_mode == open_mode, updated)
+        };
+        let open_modes = row![
+            text(strings.open_in).size(16).width(Length::Fill),
+            open_mode(strings.new_tab, OpenMode::NewTab),
+            open_mode(strings.front_tab, OpenMode::Reuse),
+        ]
+        .spacing(15)
+        .align_items(Alignment::Center);
+
+        let density = |label: &str, density: Density| {
+            let updated = Preferences {
+                density,
+                ..preferences.clone()
+            };
+            choice(label, preferences.density == density, updated)
+        };
+        let densities = row![
+            text(strings.density).size(16).width(Length::Fill),
+            density(strings.comfortable, Density::Comfortable),
+            density(strings.compact, Density::Compact),
+        ]
+        .spacing(15)
+        .align_items(Alignment::Center);
+
+        let history_limit = |history_limit: usize| {
+            let updated = Preferences {
+                history_limit,
+                ..preferences.clone()
+            };
+            let selected = preferences.history_limit == history_limit;
+            choice(&history_limit.to_string(), selected, updated)
+        };
+        let history_limits = row![
+            text(strings.history_limit).size(16).width(Length::Fill),
+            history_limit(25),
+            history_limit(MAX_HISTORY),
+            history_limit(100),
+            history_limit(200),
+        ]
+        .spacing(15)
+        .align_items(Alignment::Center);
+
+        let history_group = |label: &str, history_group: HistoryGroup| {
+            let updated = Preferences {
+                history_group,
+                ..preferences.clone()
+            };
+            choice(label, preferences.history_group == history_group, updated)
+        };
+        let grouping = row![
+            text(strings.group_history).size(16).width(Length::Fill),
+            history_group(strings.group_none, HistoryGroup::None),
+            history_group(strings.by_engine, HistoryGroup::Engine),
+            history_group(strings.by_day, HistoryGroup::Day),
+        ]
+        .spacing(15)
+        .align_items(Alignment::Center);
+
+        let tab_threshold = |confirm_tab_threshold: usize| {
+            let label = strings
+                .tabs
+                .replace("{}", &confirm_tab_threshold.to_string());
+            let selected = preferences.confirm_tab_threshold == confirm_tab_threshold;
+            let updated = Preferences {
+                confirm_tab_threshold,
+                ..preferences.clone()
+            };
+            choice(&label, selected, updated)
+        };
+        let tab_thresholds = row![
+            text(strings.tab_threshold).size(16).width(Length::Fill),
+            tab_threshold(3),
+            tab_threshold(5),
+            tab_threshold(10),
+        ]
+        .spacing(15)
+        .align_items(Alignment::Center);
+        #[cfg(feature = "previews")]
+        let result_limit = |result_limit: usize| {
+            let updated = Preferences {
+                result_limit,
+                ..preferences.clone()
+            };
+            let selected = preferences.result_limit == result_limit;
+            choice(&result_limit.to_string(), selected, updated)
+        };
+        #[cfg(feature = "previews")]
+        let tab_thresholds = column![
+            tab_thresholds,
+            row![
+                text(strings.results_listed).size(16).width(Length::Fill),
+                result_limit(5),
+                result_limit(10),
+                result_limit(results::MAX_RESULTS),
+            ]
+            .spacing(15)
+            .align_items(Alignment::Center),
+        ]
+        .spacing(20);
+
+        let counts: Vec<String> = self
+            .preferences
+            .engines()
+            .into_iter()
+            .map(|engine| (engine.name(), engine.id()))
+            .chain(
+                self.custom_engines
+                    .iter()
+                    .map(|engine| (engine.name.as_str(), engine.name.as_str())),
+            )
+            .map(|(name, id)| {
+                let count = self.engine_searches.get(id).copied();
+                format!("{name}: {}", count.unwrap_or(0))
+            })
+            .collect();
+        let engine_searches = row![
+            text(strings.searches_per_engine)
+                .size(16)
+                .width(Length::Fill),
+            text(counts.join(", ")).size(14).style(ModernColor::Muted),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        let window = row![
+            text(strings.window).size(16).width(Length::Fill),
+            button(text(strings.reset).size(14))
+                .padding([4, 10])
+                .on_press(Message::ResetWindow)
+                .style(ModernButton::Secondary),
+        ]
+        .align_items(Alignment::Center);
+
+        let actions = row![
+            button(text(strings.export_theme).size(16))
+                .padding([8, 20])
+                .on_press(Message::ExportTheme)
+                .style(ModernButton::Secondary),
+            button(text(strings.export_results).size(16))
+                .padding([8, 20])
+                .on_press(Message::ExportResultsCsv)
+                .style(ModernButton::Secondary),
+            button(text(strings.back).size(16))
+                .padding([8, 20])
+                .on_press(Message::NavigateTo(Screen::Main))
+                .style(ModernButton::Principal),
+        ]
+        .spacing(10);
+
+        let mut content = column![
+            title,
+            options,
+            themes,
+            accents,
+            tag_sizes,
+            font_scales,
+            densities,
+            languages,
+            default_engines,
+            history_limits,
+            tab_thresholds,
+            open_modes,
+            grouping,
+            order,
+            engine_searches,
+            window,
+            horizontal_rule(1),
+            actions
+        ]
+        .width(450)
+        .spacing(20);
+        if let Some(msg) = &self.toast {
+            content = content.push(toast_message(msg));
+        }
+
+        container(content)
+            .padding(10)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    }
+
+    /// History entry with the given [`HistoryEntry::id`], if it's still there.
+    fn entry(&mut self, id: u64) -> Option<&mut HistoryEntry> {
+        self.searches.iter_mut().find(|entry| entry.id == id)
+    }
+
+    /// Whether `entry` is listed with the current history filter.
+    fn filtered(&self, entry: &HistoryEntry) -> bool {
+        history::matches(&entry.query, &self.history_filter)
+    }
+
+    /// The only selected engine, `None` when there are several or none.
+    fn engine(&self) -> Option<SearchEngine> {
+        let mut engines = self.engines.iter();
+        match (engines.next(), engines.next()) {
+            (Some(engine), None) => Some(*engine),
+            _ => None,
+        }
+    }
+
+    /// Selects only `engine`, or every engine when `None`.
+    fn select_engine(&mut self, engine: Option<SearchEngine>) {
+        self.engines = engine.into_iter().collect();
+        self.custom_selected.clear();
+    }
+
+    /// Custom engine named `name`, if it's still on the config.
+    fn custom_engine(&self, name: &str) -> Option<&CustomEngine> {
+        self.custom_engines
+            .iter()
+            .find(|engine| engine.name == name)
+    }
+
+    /// Splits a query without an engine in one per selected engine, the built-in ones
+    /// first and then the custom ones. With none selected it goes to every engine.
+    fn split_engines(&self, context: QueryContext) -> Vec<QueryContext> {
+        if context.engine.is_some() {
+            return vec![context];
+        }
+        let engines = self
+            .preferences
+            .engines()
+            .into_iter()
+            .filter(|engine| self.engines.contains(engine))
+            .map(|engine| QueryContext {
+                engine: Some(engine),
+                ..context.clone()
+            });
+        let custom = self
+            .custom_engines
+            .iter()
+            .filter(|engine| self.custom_selected.contains(&engine.name))
+            .map(|engine| QueryContext {
+                custom: Some(engine.name.clone()),
+                ..context.clone()
+            });
+        let split: Vec<QueryContext> = engines.chain(custom).collect();
+        if split.is_empty() {
+            vec![context]
+        } else {
+            split
+        }
+    }
+
+    /// Logo of the engine tag, the bundled image or else its downloaded favicon.
+    fn logo(&self, engine: SearchEngine) -> Option<image::Handle> {
+        let logo = self.logos.get(&engine).cloned();
+        #[cfg(feature = "favicons")]
+        let logo = logo.or_else(|| self.favicons.get(engine.id()).cloned());
+        logo
+    }
+
+    fn set_query(&mut self, query: String) {
+        if query != self.inputs.query {
+            self.opened.reset();
+            #[cfg(feature = "spellcheck")]
+            {
+                self.correction = spellcheck::correct(&query);
+            }
+        }
+        self.inputs.query = query
+    }
+
+    /// Runs `request` once there's a free slot, a `droppable` one only while it's
+    /// the latest waiting.
+    #[cfg(any(feature = "favicons", feature = "previews"))]
+    fn limited(&mut self, request: Command<Message>, droppable: bool) -> Command<Message> {
+        self.requests
+            .start(request, droppable)
+            .unwrap_or_else(Command::none)
+    }
+
+    /// Frees the slot of a finished request, starting the next waiting one.
+    #[cfg(any(feature = "favicons", feature = "previews"))]
+    fn request_finished(&mut self) -> Command<Message> {
+        self.requests.finish().unwrap_or_else(Command::none)
+    }
+
+    /// Searches every query and stores them in the history.
+    fn submit(&mut self, queries: Vec<QueryContext>) -> Command<Message> {
+        let mut commands = Vec::new();
+        self.removed = None;
+        for QueryContext {
+            query,
+            engine,
+            custom,
+            ..
+        } in queries
+        {
+            commands.push(self.open_search(&query, engine, custom.as_deref()));
+            // Previews only come from the built-in engines.
+            #[cfg(feature = "previews")]
+            if custom.is_none() {
+                commands.push(self.update(Message::FetchResults(query.clone(), engine)));
+            }
+            let entry = HistoryEntry {
+                custom,
+                ..HistoryEntry::new(query, engine)
+            };
+            history::record(&mut self.searches, entry);
+        }
+        history::trim(&mut self.searches, self.preferences.history_limit);
+        if self.preferences.clear_after_search {
+            self.set_query(String::new());
+            // Completions still on their way belong to the old query.
+            self.suggest_generation += 1;
+            self.suggested.clear();
+            self.suggestions_open = false;
+        }
+        commands.push(self.history_changed());
+        Command::batch(commands)
+    }
+
+    /// Opens `link` as it is, without searching it.
+    fn open_link(&mut self, link: &str) -> Command<Message> {
+        self.open_url(link.to_string(), self.preferences.open_mode)
+    }
+
+    /// Language shown until the preferences pick one, headless apps always run in
+    /// English.
+    fn system_lang(&self) -> Lang {
+        if self.headless {
+            Lang::En
+        } else {
+            Lang::system()
+        }
+    }
+
+    /// Opens `url` in the browser in the background, or only records it when headless.
+    fn open_url(&mut self, url: String, mode: OpenMode) -> Command<Message> {
+        if self.headless {
+            self.opened_urls.push(url);
+            return Command::none();
+        }
+        self.opening += 1;
+        Command::perform(
+            opener::open_async(url, self.preferences.background_open, mode),
+            Message::BrowserOpened,
+        )
+    }
+
+    /// Opens `query` on the `custom` engine, else on `engine` or on
+    /// [`SearchEngine::ALL`] when it's `None`.
+    ///
+    /// Custom engines that were removed from the config open nothing.
+    fn open_search(
+        &mut self,
+        query: &str,
+        engine: Option<SearchEngine>,
+        custom: Option<&str>,
+    ) -> Command<Message> {
+        if let Some(name) = custom {
+            let Some(url) = self.custom_engine(name).map(|engine| engine.url(query)) else {
+                return Command::none();
+            };
+            if self.preferences.dedupe_opens && !self.opened.allow(&url, Instant::now()) {
+                return Command::none();
+            }
+            *self.engine_searches.entry(name.to_string()).or_default() += 1;
+            return self.open_url(url, self.preferences.open_mode);
+        }
+        let engines = match engine {
+            Some(engine) => vec![engine],
+            None => SearchEngine::ALL.to_vec(),
+        };
+        let now = Instant::now();
+        let mut commands = Vec::new();
+        for engine in engines {
+            let url = engine.url(query);
+            if self.preferences.dedupe_opens && !self.opened.allow(&url, now) {
+                continue;
+            }
+            *self
+                .engine_searches
+                .entry(engine.id().to_string())
+                .or_default() += 1;
+            // Only the first search can take the reused tab, the rest get their own.
+            let mode = if commands.is_empty() {
+                self.preferences.open_mode
+            } else {
+                OpenMode::NewTab
+            };
+            if self.headless {
+                self.opened_urls.push(url);
+                commands.push(Command::none());
+                continue;
+            }
+            self.opening += 1;
+            commands.push(Command::perform(
+                opener::open_query(
+                    query.to_string(),
+                    engine,
+                    self.preferences.background_open,
+                    mode,
+                ),
+                Message::BrowserOpened,
+            ));
+        }
+        Command::batch(commands)
+    }
+
+    /// Whether any search is still being opened.
+    fn is_loading(&self) -> bool {
+        self.opening > 0
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.saved_version < self.history_version
+    }
+
+    /// Marks the history as modified, it's saved once `history_save_delay_ms` pass.
+    fn history_changed(&mut self) -> Command<Message> {
+        self.history_version += 1;
+        if self.preferences.history_save_delay_ms == 0 {
+            return self.flush_history();
+        }
+        Command::none()
+    }
+
+    /// Saves the history in the background, unless it's already being saved.
+    fn flush_history(&mut self) -> Command<Message> {
+        if !self.history_loaded || self.flushed_version >= self.history_version {
+            return Command::none();
+        }
+        let version = self.history_version;
+        self.flushed_version = version;
+        Command::perform(
+            storage::save_history(storage::history_path(), self.searches.clone()),
+            move |result| Message::HistorySaved(version, result),
+        )
+    }
+
+    /// History in the order it's listed, see [`history::rank`].
+    #[cfg(test)]
+    fn ranked_history(&self) -> Vec<&HistoryEntry> {
+        history::rank(&self.searches)
+            .into_iter()
+            .map(|id| &self.searches[id])
+            .collect()
+    }
+
+    /// Completions for the query being typed, built from every enabled source.
+    fn suggestions(&self) -> Vec<String> {
+        #[allow(unused_mut)]
+        let mut suggestions: Vec<String> =
+            history::suggest(&self.searches, &self.inputs.query, MAX_SUGGESTIONS)
+                .into_iter()
+                .map(str::to_string)
+                .collect();
+        #[cfg(feature = "symbols")]
+        {
+            let query = &self.inputs.query;
+            let start = query
+                .char_indices()
+                .rev()
+                .find(|(_, c)| c.is_whitespace())
+                .map_or(0, |(index, c)| index + c.len_utf8());
+            suggestions.extend(
+                self.symbols
+                    .complete(&query[start..], MAX_SUGGESTIONS)
+                    .into_iter()
+                    .map(|symbol| format!("{}{symbol}", &query[..start])),
+            );
+        }
+        suggestions
+    }
+}
+
+/// Indexes the project symbols on a blocking thread.
+#[cfg(feature = "symbols")]
+async fn index_symbols(root: PathBuf) -> Arc<SymbolIndex> {
+    tokio::task::spawn_blocking(move || SymbolIndex::build(&root))
+        .await
+        .map(Arc::new)
+        .unwrap_or_default()
+}
+
+/// Rings the terminal bell, the OS plays its alert sound when it's enabled.
+///
+/// Only audible when the app was launched from a terminal.
+#[cfg(feature = "sound")]
+fn beep() {
+    use std::io::Write;
+
+    let mut stderr = std::io::stderr();
+    let _ = stderr.write_all(b"\x07").and_then(|_| stderr.flush());
+}
+
+/// Size `base` multiplied by the `font_scale` preference or the density factor.
+fn scaled(base: u16, scale: f32) -> u16 {
+    (f32::from(base) * scale).round() as u16
+}
+
+/// Decodes the image shipped for the engine on a blocking thread, so large logos
+/// don't hold up the first frame. Engines without one get no command.
+fn load_logo(engine: SearchEngine) -> Option<Command<Message>> {
+    let bytes = engine.image()?;
+    Some(Command::perform(
+        async move {
+            tokio::task::spawn_blocking(move || decode_logo(bytes))
+                .await
+                .unwrap_or_else(|_| image::Handle::from_memory(bytes))
+        },
+        move |handle| Message::LogoLoaded(engine, handle),
+    ))
+}
+
+/// Pixels of a bundled logo, or its raw bytes for the renderer to try when they
+/// can't be decoded here.
+fn decode_logo(bytes: &'static [u8]) -> image::Handle {
+    match ::image::load_from_memory(bytes) {
+        Ok(decoded) => {
+            let rgba = decoded.into_rgba8();
+            image::Handle::from_pixels(rgba.width(), rgba.height(), rgba.into_raw())
+        }
+        Err(_) => image::Handle::from_memory(bytes),
+    }
+}
+
+/// Downloads, or reads from the cache, the favicon of the site of `url`, stored as
+/// `id`.
+///
+/// The engine shows its avatar until it's loaded, or when it fails.
+#[cfg(feature = "favicons")]
+fn fetch_favicon(id: String, url: String) -> Command<Message> {
+    Command::perform(crate::favicon::load(id.clone(), url), move |result| {
+        Message::FaviconLoaded(id.clone(), result.map(image::Handle::from_memory))
+    })
+}
+
+/// Asks for a destination file and writes `contents` on it.
+///
+/// Returns `Ok(None)` when the dialog is cancelled.
+async fn save_file(
+    file_name: &'static str,
+    extension: &'static str,
+    contents: String,
+) -> Result<Option<PathBuf>, SearchError> {
+    let Some(file) = rfd::AsyncFileDialog::new()
+        .set_file_name(file_name)
+        .add_filter(extension, &[extension])
+        .save_file()
+        .await
+    else {
+        return Ok(None);
+    };
+    let path = file.path().to_path_buf();
+    tokio::fs::write(&path, contents).await?;
+    Ok(Some(path))
+}
+
+/// Wraps a message into a [`Command`] so it's dispatched on the next update.
+fn message(message: Message) -> Command<Message> {
+    Command::perform(async move { message }, std::convert::identity)
+}
+
+/// Id of the query input, focused on launch so the app is ready to type.
+fn query_input() -> text_input::Id {
+    text_input::Id::new("query")
+}
+
+fn history_list() -> scrollable::Id {
+    scrollable::Id::new("history")
+}
+
+fn icon(unicode: char, size: impl Into<Pixels>) -> Text<'static> {
+    Text::new(unicode.to_string())
+        .font(ICON_FONT)
+        .horizontal_alignment(Horizontal::Center)
+        .size(size)
+}
+
+/// History row, `compact` rows only show their actions while hovered.
+///
+/// Right clicking the row opens its context menu, which can toggle the `expanded`
+/// mode showing every word of the query as a chip to search it alone. The info
+/// action opens the entry details while `detail` is set.
+///
+/// Parts of the query matching `filter` are drawn with the accent.
+#[allow(clippy::too_many_arguments)]
+fn historial_text<'a>(
+    entry: &'a HistoryEntry,
+    filter: &str,
+    now: SystemTime,
+    theme: ModernTheme,
+    compact: bool,
+    expanded: bool,
+    detail: bool,
+    scale: f32,
+    strings: &'static Strings,
+) -> Element<'a, Message> {
+    let id = entry.id;
+    let query = entry.query.as_str();
+    let row = Row::new()
+        .push(badge(entry.engine.map(|engine| engine.color_for(theme))))
+        .push(horizontal_space(10))
+        .push(
+            button(Row::with_children(
+                history::highlight(query, filter)
+                    .into_iter()
+                    .map(|(span, matched)| {
+                        Text::new(span)
+                            .size(scaled(18, scale))
+                            .style(if matched {
+                                ModernColor::Accent
+                            } else {
+                                ModernColor::Muted
+                            })
+                            .into()
+                    })
+                    .collect(),
+            ))
+            .style(ModernButton::Text)
+            .on_press(Message::RerunSearch(id)),
+        )
+        .push(
+            text(history::ago(entry.searched_at, now, strings))
+                .size(scaled(14, scale))
+                .style(ModernColor::Muted),
+        )
+        .push(horizontal_space(10))
+        .push(row_action('\u{F290}', Message::CopySearch(id), compact))
+        .push(
+            popover(
+                row_action('\u{F431}', Message::ShowDetail(id), compact),
+                detail.then(|| history_detail(entry, strings)),
+            )
+            .on_dismiss(Message::HideDetail),
+        )
+        .push(row_action(
+            if entry.pinned { '\u{F4EC}' } else { '\u{F4ED}' },
+            Message::TogglePin(id),
+            compact,
+        ))
+        .align_items(Alignment::Center);
+
+    let content: Element<Message> = if expanded {
+        let words = Row::with_children(
+            query
+                .split_whitespace()
+                .map(|word| {
+                    button(Text::new(word).size(scaled(14, scale)))
+                        .padding([2, 8])
+                        .style(ModernButton::Tag(entry.engine))
+                        .on_press(Message::SetSearch(word.to_string()))
+                        .into()
+                })
+                .collect(),
+        )
+        .spacing(5);
+        column![row, words].spacing(5).into()
+    } else {
+        row.into()
+    };
+
+    mouse_area(content)
+        .on_right_press(Message::OpenMenu(id))
+        .into()
+}
+
+/// Context menu of a history entry, opened by right clicking it.
+fn history_menu<'a>(
+    entry: &'a HistoryEntry,
+    expanded: bool,
+    strings: &'static Strings,
+) -> Element<'a, Message> {
+    let id = entry.id;
+    let item = |label: &'static str, message: Message| {
+        button(text(label).size(14))
+            .padding([4, 12])
+            .width(Length::Fill)
+            .style(ModernButton::Text)
+            .on_press(message)
+    };
+
+    container(
+        column![
+            item(strings.copy, Message::CopySearch(id)),
+            item(strings.rerun, Message::RerunSearch(id)),
+            item(
+                if entry.pinned {
+                    strings.unpin
+                } else {
+                    strings.pin
+                },
+                Message::TogglePin(id)
+            ),
+            item(
+                if expanded {
+                    strings.hide_words
+                } else {
+                    strings.show_words
+                },
+                Message::ToggleExpand(id)
+            ),
+            item(strings.delete, Message::RemoveSearch(id)).style(ModernButton::Danger),
+        ]
+        .spacing(2),
+    )
+    .padding(6)
+    .width(140)
+    .style(ModernContainer::Popover)
+    .into()
+}
+
+/// Popover with the metadata and actions of a history entry.
+fn history_detail<'a>(entry: &'a HistoryEntry, strings: &'static Strings) -> Element<'a, Message> {
+    let id = entry.id;
+    let engine = match (&entry.custom, entry.engine) {
+        (Some(custom), _) => custom.as_str(),
+        (None, engine) => engine.map_or(strings.every_engine, |engine| engine.name()),
+    };
+    let action = |label: &'static str, message: Message| {
+        button(Text::new(label).size(14))
+            .padding([4, 10])
+            .style(ModernButton::Secondary)
+            .on_press(message)
+    };
+
+    container(
+        column![
+            Text::new(entry.query.as_str()).size(18),
+            text(strings.engine.replace("{}", engine)).size(14),
+            text(
+                strings
+                    .searched_at
+                    .replace("{}", &export::timestamp(entry.searched_at))
+            )
+            .size(14),
+            text(strings.reruns.replace("{}", &entry.reruns.to_string())).size(14),
+            row![
+                action(strings.copy, Message::CopySearch(id)),
+                action(strings.copy_link, Message::CopyLink(id)),
+                action(strings.rerun, Message::RerunSearch(id)),
+                action(strings.edit, Message::EditSearch(id)),
+                action(strings.delete, Message::RemoveSearch(id)).style(ModernButton::Danger),
+            ]
+            .spacing(5),
+        ]
+        .spacing(8),
+    )
+    .padding(12)
+    .max_width(320)
+    .style(ModernContainer::Popover)
+    .into()
+}
+
+fn row_action(unicode: char, message: Message, compact: bool) -> Element<'static, Message> {
+    if compact {
+        button(icon(unicode, 18))
+            .on_press(message)
+            .style(ModernButton::Reveal)
+            .into()
+    } else {
+        button(icon(unicode, 18).style(ModernColor::Muted))
+            .on_press(message)
+            .style(ModernButton::Text)
+            .into()
+    }
+}
+
+/// Item of the history list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HistoryItem {
+    /// Header of an engine section, with its entry count and whether it's collapsed.
+    Group(Option<SearchEngine>, usize, bool),
+    /// Title splitting the pinned entries from the rest.
+    Section(&'static str),
+    /// Header of the searches of a day, see [`history::day`].
+    Day(u64),
+    /// Position of an entry on the history.
+    Entry(usize),
+}
+
+/// Header of the history section of `engine`, clicking it collapses the section.
+fn group_header(
+    engine: Option<SearchEngine>,
+    count: usize,
+    collapsed: bool,
+    theme: ModernTheme,
+    strings: &Strings,
+) -> Element<'static, Message> {
+    let name = engine.map_or(strings.all_engines, |engine| engine.name());
+    button(
+        row![
+            icon(if collapsed { '\u{F285}' } else { '\u{F282}' }, 12),
+            badge(engine.map(|engine| engine.color_for(theme))),
+            text(format!("{name} ({count})")).size(14),
+        ]
+        .spacing(8)
+        .align_items(Alignment::Center),
+    )
+    .padding([4, 0])
+    .style(ModernButton::Text)
+    .on_press(Message::ToggleGroup(engine))
+    .into()
+}
+
+/// Latest distinct searches, one click away from being searched again.
+fn recent_chips(searches: &[HistoryEntry]) -> Row<'static, Message> {
+    let mut seen = Vec::with_capacity(RECENT_CHIPS);
+    let chips = searches
+        .iter()
+        .filter(|entry| {
+            let new = !seen.contains(&entry.query.as_str());
+            if new {
+                seen.push(entry.query.as_str());
+            }
+            new
+        })
+        .take(RECENT_CHIPS)
+        .map(|entry| {
+            let label = if entry.query.chars().count() > CHIP_LENGTH {
+                let cut: String = entry.query.chars().take(CHIP_LENGTH - 1).collect();
+                format!("{cut}…")
+            } else {
+                entry.query.clone()
+            };
+            button(text(label).size(14))
+                .padding([3, 10])
+                .style(ModernButton::Tag(entry.engine))
+                .on_press(Message::SetSearch(entry.query.clone()))
+                .into()
+        })
+        .collect();
+    Row::with_children(chips).spacing(5)
+}
+
+/// Quick filters of the selected engine, each adding its operator to the query.
+fn operator_chips(engine: SearchEngine, query: &str) -> Row<'static, Message> {
+    let chips = engine
+        .operators()
+        .iter()
+        .map(|operator| {
+            let chip = button(text(*operator).size(14))
+                .padding([3, 10])
+                .style(ModernButton::Secondary);
+            // Operators already in the query have nothing left to add.
+            let added = query.split_whitespace().any(|word| word == *operator);
+            if added {
+                chip.into()
+            } else {
+                chip.on_press(Message::QueryChange(query::with_operator(query, operator)))
+                    .into()
+            }
+        })
+        .collect();
+    Row::with_children(chips).spacing(5)
+}
+
+/// Outlines `content` while the focus ring is on it, keeping room for the outline
+/// either way so nothing moves.
+fn focus_outline<'a>(
+    content: impl Into<Element<'a, Message>>,
+    focused: bool,
+) -> Element<'a, Message> {
+    let content = container(content).padding(2);
+    if focused {
+        content.style(ModernContainer::Highlight).into()
+    } else {
+        content.into()
+    }
+}
+
+/// Dropdown listing the completions of the query.
+fn show_suggestions(suggestions: Vec<String>) -> Element<'static, Message> {
+    let list = Column::with_children(
+        suggestions
+            .into_iter()
+            .map(|suggestion| {
+                button(text(&suggestion).size(16).style(ModernColor::Muted))
+                    .style(ModernButton::Text)
+                    .width(Length::Fill)
+                    .on_press(Message::QueryChange(suggestion))
+                    .into()
+            })
+            .collect(),
+    )
+    .spacing(2);
+    container(list)
+        .padding(8)
+        .width(610)
+        .style(ModernContainer::Card)
+        .into()
+}
+
+/// Star under an engine tag that adds it to or removes it from the favorites.
+fn favorite_star(engine: SearchEngine, favorite: bool) -> Element<'static, Message> {
+    let glyph = if favorite { '\u{F586}' } else { '\u{F588}' };
+    button(icon(glyph, 12))
+        .padding(0)
+        .style(ModernButton::Text)
+        .on_press(Message::ToggleFavorite(engine))
+        .into()
+}
+
+/// Compact row with the favorite engines, always one click away.
+fn favorites_bar(favorites: &[SearchEngine]) -> Row<'static, Message> {
+    Row::with_children(
+        favorites
+            .iter()
+            .map(|engine| {
+                button(text(engine.name()).size(14))
+                    .padding([3, 10])
+                    .style(ModernButton::Tag(Some(*engine)))
+                    .on_press(Message::TagSelected(*engine))
+                    .into()
+            })
+            .collect(),
+    )
+    .spacing(5)
+}
+
+/// Offers to bring back `query`, left in the input when the app was closed.
+fn restore_prompt<'a>(
+    base: Element<'a, Message>,
+    query: &str,
+    strings: &Strings,
+) -> Element<'a, Message> {
+    let actions = row![
+        horizontal_space(Length::Fill),
+        button(text(strings.dismiss).size(16))
+            .padding([8, 20])
+            .style(ModernButton::Secondary)
+            .on_press(Message::DismissLastQuery),
+        button(text(strings.restore).size(16))
+            .padding([8, 20])
+            .style(ModernButton::Principal)
+            .on_press(Message::RestoreLastQuery),
+    ]
+    .spacing(10);
+    let content = column![
+        text(query.to_string()).size(16).style(ModernColor::Muted),
+        actions
+    ]
+    .spacing(20);
+    modal(
+        base,
+        strings.restore_title,
+        content,
+        Message::DismissLastQuery,
+    )
+    .into()
+}
+
+/// Removes the stored last query, once restored or dismissed.
+fn forget_last_query() -> Command<Message> {
+    Command::perform(
+        storage::save_last_query(storage::last_query_path(), None),
+        Message::LastQuerySaved,
+    )
+}
+
+/// Browser tabs opened by searching `queries`, one per engine each goes to.
+fn tabs(queries: &[QueryContext]) -> usize {
+    queries
+        .iter()
+        .map(|context| match (context.engine, &context.custom) {
+            (None, None) => SearchEngine::ALL.len(),
+            _ => 1,
+        })
+        .sum()
+}
+
+/// Asks before a submit opens `tabs` browser tabs at once.
+fn confirm_tabs<'a>(
+    base: Element<'a, Message>,
+    tabs: usize,
+    strings: &Strings,
+) -> Element<'a, Message> {
+    let actions = row![
+        horizontal_space(Length::Fill),
+        button(text(strings.cancel).size(16))
+            .padding([8, 20])
+            .style(ModernButton::Secondary)
+            .on_press(Message::CancelOpenTabs),
+        button(text(strings.open).size(16))
+            .padding([8, 20])
+            .style(ModernButton::Principal)
+            .on_press(Message::ConfirmOpenTabs),
+    ]
+    .spacing(10);
+    let content = column![text(strings.open_tabs_notice).size(16), actions].spacing(20);
+    modal(
+        base,
+        &strings.open_tabs.replace("{}", &tabs.to_string()),
+        content,
+        Message::CancelOpenTabs,
+    )
+    .into()
+}
+
+/// Title of the history list with its filter, the repeat, export and clear buttons
+/// are disabled and the filter hidden while it's empty.
+fn history_header<'a>(
+    empty: bool,
+    confirming: bool,
+    filter: &'a str,
+    strings: &Strings,
+) -> Row<'a, Message> {
+    let mut header = row![text(strings.history).size(16).width(Length::Fill)]
+        .spacing(10)
+        .width(610)
+        .align_items(Alignment::Center);
+    let mut repeat = button(text(strings.repeat_last).size(14))
+        .padding([3, 10])
+        .style(ModernButton::Secondary);
+    let mut export = button(text(strings.export).size(14))
+        .padding([3, 10])
+        .style(ModernButton::Secondary);
+    let label = if confirming {
+        strings.confirm_clear
+    } else {
+        strings.clear_all
+    };
+    let mut clear = button(text(label).size(14))
+        .padding([3, 10])
+        .style(ModernButton::Danger);
+    if !empty {
+        header = header.push(
+            text_input(strings.filter, filter)
+                .on_input(Message::FilterHistory)
+                .padding([4, 12])
+                .size(14)
+                .width(200),
+        );
+        repeat = repeat.on_press(Message::RepeatLast);
+        export = export.on_press(Message::ExportHistory);
+        clear = clear.on_press(Message::RequestClearHistory);
+    }
+    header.push(repeat).push(export).push(clear)
+}
+
+/// Warns that the history is full, offering to make room for more searches.
+fn history_limit_notice(
+    len: usize,
+    preferences: &Preferences,
+    strings: &Strings,
+) -> Row<'static, Message> {
+    let mut increased = preferences.clone();
+    increased.history_limit += MAX_HISTORY;
+    let notice = strings
+        .history_full
+        .replace("{len}", &len.to_string())
+        .replace("{limit}", &preferences.history_limit.to_string());
+    row![
+        text(notice)
+            .size(14)
+            .width(Length::Fill)
+            .style(ModernColor::Muted),
+        button(text(strings.increase_limit).size(14))
+            .padding([3, 10])
+            .style(ModernButton::Secondary)
+            .on_press(Message::PreferencesChanged(increased)),
+    ]
+    .width(610)
+    .align_items(Alignment::Center)
+}
+
+/// Muted count of the searches in the history, `shown` of them when filtered.
+fn history_count(
+    total: usize,
+    shown: usize,
+    scale: f32,
+    strings: &Strings,
+) -> Container<'static, Message, Renderer> {
+    let searches = strings.searches[usize::from(total != 1)];
+    let label = if shown == total {
+        format!("{total} {searches}")
+    } else {
+        let count = strings
+            .shown_of
+            .replace("{shown}", &shown.to_string())
+            .replace("{total}", &total.to_string());
+        format!("{count} {searches}")
+    };
+    container(
+        text(label)
+            .size(scaled(14, scale))
+            .style(ModernColor::Empty),
+    )
+    .width(610)
+    .center_x()
+}
+
+/// Top hits of the last search, clicking one opens it. Failed fetches offer to
+/// fetch `query` again, nothing is shown before the first search.
+///
+/// Only the first `limit` hits are listed, with a count of the fetched ones when
+/// some are left out.
+#[cfg(feature = "previews")]
+fn results_view(
+    results: &ResultState,
+    query: Option<&(String, Option<SearchEngine>)>,
+    limit: usize,
+    strings: &Strings,
+) -> Option<Container<'static, Message, Renderer>> {
+    let rows: Vec<Element<Message>> = match results {
+        ResultState::Idle => return None,
+        ResultState::Loading => vec![results_notice(strings.loading_results).into()],
+        ResultState::Empty => vec![results_notice(strings.no_results).into()],
+        ResultState::Error(err) => {
+            let mut retry = button(text(strings.retry).size(14))
+                .padding([3, 10])
+                .style(ModernButton::Secondary);
+            if let Some((query, engine)) = query {
+                retry = retry.on_press(Message::FetchResults(query.clone(), *engine));
+            }
+            vec![row![results_notice(err), retry]
+                .align_items(Alignment::Center)
+                .into()]
+        }
+        ResultState::Loaded(items) => {
+            let mut rows: Vec<Element<Message>> = items
+                .iter()
+                .take(limit)
+                .map(|item| {
+                    button(column![
+                        text(&item.title).size(16),
+                        text(&item.url).size(12).style(ModernColor::Muted),
+                    ])
+                    .width(Length::Fill)
+                    .padding([4, 0])
+                    .style(ModernButton::Text)
+                    .on_press(Message::OpenResult(item.url.clone()))
+                    .into()
+                })
+                .collect();
+            if items.len() > limit {
+                let shown = strings
+                    .showing_results
+                    .replace("{shown}", &rows.len().to_string())
+                    .replace("{total}", &items.len().to_string());
+                rows.push(text(shown).size(12).style(ModernColor::Muted).into());
+            }
+            rows
+        }
+    };
+    Some(
+        container(column(rows).spacing(5))
+            .width(610)
+            .padding([15, 30])
+            .style(ModernContainer::Card),
+    )
+}
+
+/// Muted message filling the results card, like [`empty_message`] does the history.
+#[cfg(feature = "previews")]
+fn results_notice(msg: &str) -> Text<'static> {
+    text(msg)
+        .width(Length::Fill)
+        .size(16)
+        .horizontal_alignment(Horizontal::Center)
+        .style(ModernColor::Muted)
+}
+
+fn empty_message(msg: &str, scale: f32, density: f32) -> Container<'_, Message, Renderer> {
+    container(
+        text(msg)
+            .width(Length::Fill)
+            .size(scaled(20, scale))
+            .vertical_alignment(Vertical::Center)
+            .horizontal_alignment(Horizontal::Center)
+            .style(ModernColor::Empty),
+    )
+    .width(610)
+    .height(scaled(200, density))
+    .center_x()
+    .center_y()
+    .style(ModernContainer::Historial)
+}
+
+/// Toggler that emits the preferences with the option changed by `set`.
+fn preference<'a>(
+    label: &str,
+    preferences: &Preferences,
+    value: bool,
+    set: fn(&mut Preferences, bool),
+) -> Element<'a, Message> {
+    let preferences = preferences.clone();
+    toggler(label.to_string(), value, move |value| {
+        let mut preferences = preferences.clone();
+        set(&mut preferences, value);
+        Message::PreferencesChanged(preferences)
+    })
+    .size(20)
+    .text_size(16)
+    .into()
+}
+
+/// Settings row to move an engine tag, the arrows are disabled at the edges.
+fn engine_order_row<'a>(
+    preferences: &Preferences,
+    engine: SearchEngine,
+    theme: ModernTheme,
+    up: bool,
+    down: bool,
+) -> Element<'a, Message> {
+    let arrow = |unicode, forward, enabled| {
+        let mut moved = preferences.clone();
+        moved.move_engine(engine, forward);
+        let arrow = button(icon(unicode, 12))
+            .height(26)
+            .width(26)
+            .padding(5)
+            .style(ModernButton::Secondary);
+        if enabled {
+            arrow.on_press(Message::PreferencesChanged(moved))
+        } else {
+            arrow
+        }
+    };
+
+    row![
+        badge(Some(engine.color_for(theme))),
+        text(engine.name()).size(16).width(Length::Fill),
+        arrow('\u{F286}', false, up),
+        arrow('\u{F282}', true, down),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center)
+    .into()
+}
+
+/// Themed radio for one value of an exclusive preference, picking it stores `updated`.
+fn choice<'a>(label: &str, selected: bool, updated: Preferences) -> Element<'a, Message> {
+    radio(label, true, Some(selected), |_| {
+        Message::PreferencesChanged(updated)
+    })
+    .size(16)
+    .text_size(14)
+    .into()
+}
+
+fn toast_message(msg: &str) -> Container<'_, Message, Renderer> {
+    container(text(msg).size(16).style(ModernColor::Muted))
+        .padding([8, 20])
+        .center_x()
+        .style(ModernContainer::Input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced_native::widget::{
+        button, scrollable, text_input,
+        tree::{Tag, Tree},
+    };
+
+    fn app() -> App {
+        App::headless()
+    }
+
+    /// Counts the widgets of the tree whose state matches the given tag.
+    ///
+    /// Texts and images are stateless, so the structure is checked through the
+    /// interactive widgets surrounding them.
+    fn count(tree: &Tree, tag: Tag) -> usize {
+        usize::from(tree.tag == tag) + tree.children.iter().map(|c| count(c, tag)).sum::<usize>()
+    }
+
+    #[test]
+    fn submitting_blank_query_does_nothing() {
+        let mut app = app();
+        let _ = app.update(Message::QueryChange("   ".to_string()));
+        let _ = app.update(Message::OnPressing);
+
+        assert!(app.searches.is_empty());
+        assert!(app.opened_urls.is_empty());
+    }
+
+    #[test]
+    fn placeholder_stays_between_updates() {
+        let mut app = app();
+        let placeholder = app.placeholder;
+        assert!(placeholder < Lang::En.strings().placeholders.len());
+
+        for query in ["h", "ho", "how"] {
+            let _ = app.update(Message::QueryChange(query.to_string()));
+            let _ = app.view();
+            assert_eq!(app.placeholder, placeholder);
+        }
+    }
+
+    #[test]
+    fn next_theme_cycles_every_theme() {
+        let mut app = app();
+        let mut seen = vec![app.theme];
+        for _ in 1..ModernTheme::ALL.len() {
+            let _ = app.update(Message::NextTheme);
+            seen.push(app.theme);
+        }
+        assert_eq!(seen, ModernTheme::ALL);
+
+        let _ = app.update(Message::NextTheme);
+        assert_eq!(app.theme, ModernTheme::Dark);
+    }
+
+    #[test]
+    fn custom_engines_open_their_url() {
+        let mut app = app();
+        let preferences = Preferences {
+            custom_engines: vec![
+                CustomEngine {
+                    name: "docs".into(),
+                    url: "https://docs.rs".into(),
+                    color: None,
+                },
+                CustomEngine {
+                    name: "crates.io".into(),
+                    url: "https://crates.io/search?q={query}".into(),
+                    color: Some("#3B6837".into()),
+                },
+            ],
+            ..Default::default()
+        };
+        let _ = app.update(Message::PreferencesLoaded(Ok(preferences)));
+        assert_eq!(app.custom_engines.len(), 1);
+
+        let _ = app.update(Message::CustomTagSelected("crates.io".into()));
+        let _ = app.update(Message::QueryChange("serde json;;  tokio  ".into()));
+        let _ = app.update(Message::OnPressing);
+        assert_eq!(
+            app.opened_urls,
+            [
+                "https://crates.io/search?q=serde%20json",
+                "https://crates.io/search?q=tokio"
+            ]
+        );
+        assert_eq!(app.searches[0].custom.as_deref(), Some("crates.io"));
+        assert_eq!(app.engine_searches.get("crates.io"), Some(&2));
+
+        // A bang wins over the custom engine.
+        let _ = app.update(Message::QueryChange("!so borrow".into()));
+        let _ = app.update(Message::OnPressing);
+        assert_eq!(app.searches[0].engine, Some(SearchEngine::StackOverflow));
+        assert_eq!(app.searches[0].custom, None);
+
+        let rerun = app.searches[1].id;
+        let _ = app.update(Message::RerunSearch(rerun));
+        assert_eq!(
+            app.opened_urls.last().map(String::as_str),
+            Some("https://crates.io/search?q=tokio")
+        );
+    }
+
+    #[test]
+    fn blank_search_shakes_the_input() {
+        let mut app = app();
+        let _ = app.update(Message::QueryChange("   ".into()));
+        let _ = app.update(Message::OnPressing);
+        assert_eq!(app.shake, SHAKE_FRAMES);
+        assert!(app.searches.is_empty());
+
+        for _ in 0..SHAKE_FRAMES {
+            let _ = app.update(Message::ShakeTick);
+        }
+        assert_eq!(app.shake, 0);
+
+        let _ = app.update(Message::QueryChange("rust".into()));
+        let _ = app.update(Message::OnPressing);
+        assert_eq!(app.shake, 0);
+    }
+
+    #[test]
+    fn theme_shortcut_works_while_typing() {
+        use iced::keyboard::{KeyCode, Modifiers};
+
+        let mut app = app();
+        let typed = KeyCombo::new(KeyCode::T, Modifiers::empty());
+        let _ = app.update(Message::KeyPressed(typed, event::Status::Captured));
+        assert_eq!(app.theme, ModernTheme::Dark);
+
+        let shortcut = KeyCombo::new(KeyCode::T, Modifiers::COMMAND);
+        let _ = app.update(Message::KeyPressed(shortcut, event::Status::Captured));
+        assert_eq!(app.theme, ModernTheme::Dark.next());
+    }
+
+    #[test]
+    fn saved_settings_apply_on_load() {
+        let mut app = app();
+        let _ = app.update(Message::SetTheme(ModernTheme::HighContrast));
+        assert_eq!(app.preferences.theme, Some(ModernTheme::HighContrast));
+
+        let preferences = Preferences {
+            theme: Some(ModernTheme::Light),
+            default_engine: Some("se".into()),
+            ..Preferences::default()
+        };
+        let _ = app.update(Message::PreferencesLoaded(Ok(preferences)));
+        assert_eq!(app.theme, ModernTheme::Light);
+        assert_eq!(app.engine(), Some(SearchEngine::StackExchange));
+    }
+
+    #[test]
+    fn deep_link_engine_wins_over_the_default() {
+        for default in ["so", "se"] {
+            let mut app = app();
+            let preferences = Preferences {
+                default_engine: Some(default.into()),
+                ..Preferences::default()
+            };
+            let _ = app.update(Message::PreferencesLoaded(Ok(preferences)));
+            let _ = app.update(Message::SelectEngine(SearchEngine::StackOverflow));
+            let _ = app.update(Message::SelectEngine(SearchEngine::StackOverflow));
+            assert_eq!(app.engines, HashSet::from([SearchEngine::StackOverflow]));
+        }
+    }
+
+    #[test]
+    fn language_follows_the_preferences() {
+        let mut app = app();
+        assert_eq!(app.lang, Lang::En);
+
+        let spanish = Preferences {
+            language: Some(Lang::Es),
+            ..Preferences::default()
+        };
+        let _ = app.update(Message::PreferencesChanged(spanish));
+        assert_eq!(app.lang, Lang::Es);
+
+        let _ = app.update(Message::PreferencesChanged(Preferences::default()));
+        assert_eq!(app.lang, Lang::En);
+    }
+
+    #[cfg(feature = "symbols")]
+    #[test]
+    fn symbols_complete_after_a_wide_space() {
+        let mut app = app();
+        app.symbols = Arc::new(SymbolIndex::from_source("fn bar_baz() {}"));
+        app.inputs.query = "foo\u{a0}ba".into();
+        assert_eq!(app.suggestions(), vec!["foo\u{a0}bar_baz"]);
+
+        app.inputs.query = "foo\u{3000}ba".into();
+        assert_eq!(app.suggestions(), vec!["foo\u{3000}bar_baz"]);
+    }
+
+    #[test]
+    fn preferences_are_saved_once_loaded() {
+        let mut app = app();
+        let changed = Preferences {
+            compact_rows: true,
+            ..Preferences::default()
+        };
+        let save = app.update(Message::PreferencesChanged(changed.clone()));
+        assert!(save.actions().is_empty());
+
+        let _ = app.update(Message::PreferencesLoaded(Ok(Preferences::default())));
+        let save = app.update(Message::PreferencesChanged(changed));
+        assert_eq!(save.actions().len(), 1);
+    }
+
+    #[test]
+    fn toasts_follow_the_language() {
+        let mut app = app();
+        let spanish = Preferences {
+            language: Some(Lang::Es),
+            ..Preferences::default()
+        };
+        let _ = app.update(Message::PreferencesChanged(spanish));
+        let entry = HistoryEntry::new("rust lifetimes", None);
+        let id = entry.id;
+        app.searches.push(entry);
+
+        let _ = app.update(Message::RemoveSearch(id));
+        assert_eq!(app.toast.as_deref(), Some("Búsqueda borrada"));
+
+        let offline = SearchError::Network("dns error".into());
+        let _ = app.update(Message::PreferencesSaved(Err(offline)));
+        assert_eq!(
+            app.toast.as_deref(),
+            Some("No se pudieron guardar las preferencias: network error: dns error")
+        );
+    }
+
+    #[test]
+    fn font_scale_rounds_text_sizes() {
+        assert_eq!(scaled(18, 1.0), 18);
+        assert_eq!(scaled(14, 1.25), 18);
+        assert_eq!(scaled(75, 1.5), 113);
+    }
+
+    #[test]
+    fn light_mode_toggler_flips_theme() {
+        let mut app = app();
+        app.theme = ModernTheme::ColorBlind;
+
+        let _ = app.update(light_mode(true));
+        assert_eq!(app.theme, ModernTheme::Light);
+        let _ = app.update(light_mode(false));
+        assert_eq!(app.theme, ModernTheme::Dark);
+    }
+
+    #[test]
+    fn view_keeps_core_widgets() {
+        let app = app();
+        let tree = Tree::new(app.view().as_widget());
+
+        assert_eq!(count(&tree, Tag::of::<text_input::State>()), 1);
+        // Two engine tags, the theme button and the search button.
+        assert!(count(&tree, Tag::of::<button::State>()) >= 4);
+        // Only the tag row scrolls, the empty history shows a message instead of the list.
+        assert_eq!(count(&tree, Tag::of::<scrollable::State>()), 1);
+    }
+
+    #[test]
+    fn view_shows_history_list() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("rust lifetimes", Some(SearchEngine::StackOverflow)),
+            HistoryEntry::new("iced widgets", None),
+        ];
+        let tree = Tree::new(app.view().as_widget());
+
+        // The tag row and the history list.
+        assert_eq!(count(&tree, Tag::of::<scrollable::State>()), 2);
+        // Each row has buttons to reuse the query, show its details and remove it.
+        assert!(count(&tree, Tag::of::<button::State>()) >= 4 + 3 * app.searches.len());
+    }
+
+    #[test]
+    fn full_history_offers_a_bigger_limit() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("rust lifetimes", None),
+            HistoryEntry::new("iced widgets", None),
+        ];
+        app.preferences.history_limit = 3;
+        let room_left = count(
+            &Tree::new(app.view().as_widget()),
+            Tag::of::<button::State>(),
+        );
+
+        app.preferences.history_limit = 2;
+        let full = count(
+            &Tree::new(app.view().as_widget()),
+            Tag::of::<button::State>(),
+        );
+
+        assert_eq!(full, room_left + 1);
+    }
+
+    #[test]
+    fn loaded_history_keeps_the_limit() {
+        let mut app = app();
+        app.preferences.history_limit = 2;
+        let entries = ["a", "b", "c"].map(|query| HistoryEntry::new(query, None));
+
+        let _ = app.update(Message::HistoryLoaded(Ok(entries.to_vec())));
+
+        assert_eq!(queries(app.searches.iter().collect()), ["a", "b"]);
+    }
+
+    #[test]
+    fn many_tabs_wait_for_confirmation() {
+        let mut app = app();
+        app.inputs.query = "a;; b;; c".into();
+
+        let _ = app.update(Message::OnPressing);
+        let queries: Vec<String> = app
+            .pending_tabs
+            .iter()
+            .flatten()
+            .map(|context| context.query.clone())
+            .collect();
+        assert_eq!(queries, vec!["a", "b", "c"]);
+        assert!(app.searches.is_empty());
+
+        let _ = app.update(Message::CancelOpenTabs);
+        assert!(app.pending_tabs.is_none());
+        assert!(app.searches.is_empty());
+    }
+
+    #[test]
+    fn plain_shortcuts_skip_a_focused_input() {
+        use iced::keyboard::{KeyCode, Modifiers};
+
+        let mut app = app();
+        let e = KeyCombo::new(KeyCode::E, Modifiers::empty());
+
+        let _ = app.update(Message::KeyPressed(e, event::Status::Captured));
+        assert_eq!(app.engine(), None);
+
+        let _ = app.update(Message::KeyPressed(e, event::Status::Ignored));
+        assert_eq!(app.engine(), Some(app.preferences.engines()[0]));
+    }
+
+    #[test]
+    fn digits_select_engines_outside_the_input() {
+        use iced::keyboard::{KeyCode, Modifiers};
+
+        let mut app = app();
+        let two = KeyCombo::new(KeyCode::Key2, Modifiers::empty());
+        let second = app.preferences.engines()[1];
+
+        let _ = app.update(Message::KeyPressed(two, event::Status::Captured));
+        assert!(app.engines.is_empty());
+
+        let _ = app.update(Message::KeyPressed(two, event::Status::Ignored));
+        assert_eq!(app.engines, HashSet::from([second]));
+
+        let nine = KeyCombo::new(KeyCode::Key9, Modifiers::empty());
+        let _ = app.update(Message::KeyPressed(nine, event::Status::Ignored));
+        assert_eq!(app.engines, HashSet::from([second]));
+    }
+
+    #[test]
+    fn clearing_the_query_keeps_the_engine() {
+        let mut app = app();
+        let _ = app.update(Message::TagSelected(SearchEngine::StackExchange));
+        let _ = app.update(Message::QueryChange("rust lifetimes".into()));
+
+        let _ = app.update(Message::ClearQueryOnly);
+        assert!(app.inputs.query.is_empty());
+        assert_eq!(app.engine(), Some(SearchEngine::StackExchange));
+    }
+
+    #[test]
+    fn several_engines_search_each() {
+        let mut app = app();
+        let _ = app.update(Message::TagSelected(SearchEngine::StackOverflow));
+        let _ = app.update(Message::TagSelected(SearchEngine::StackExchange));
+        assert_eq!(app.engine(), None);
+
+        let _ = app.update(Message::QueryChange("rust lifetimes".into()));
+        let _ = app.update(Message::OnPressing);
+
+        let engines: Vec<_> = app.searches.iter().map(|entry| entry.engine).collect();
+        assert_eq!(engines.len(), 2);
+        assert!(engines.contains(&Some(SearchEngine::StackOverflow)));
+        assert!(engines.contains(&Some(SearchEngine::StackExchange)));
+        assert_eq!(app.opened_urls.len(), 2);
+
+        let _ = app.update(Message::TagSelected(SearchEngine::StackOverflow));
+        assert_eq!(app.engine(), Some(SearchEngine::StackExchange));
+    }
+
+    #[test]
+    fn history_filter_keeps_the_ids() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("rust lifetimes", None),
+            HistoryEntry::new("iced widgets", None),
+            HistoryEntry::new("rust traits", None),
+        ];
+        let _ = app.update(Message::FilterHistory("rst tr".into()));
+        let shown: Vec<bool> = app
+            .searches
+            .iter()
+            .map(|entry| app.filtered(entry))
+            .collect();
+        assert_eq!(shown, [false, false, true]);
+
+        let _ = app.update(Message::RemoveSearch(app.searches[2].id));
+        assert_eq!(
+            queries(app.searches.iter().collect()),
+            ["rust lifetimes", "iced widgets"]
+        );
+        let tree = Tree::new(app.view().as_widget());
+        // The filter input stays, the list gives way to a message.
+        assert_eq!(count(&tree, Tag::of::<text_input::State>()), 2);
+    }
+
+    #[test]
+    fn removing_twice_keeps_the_other_entries() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("rust lifetimes", None),
+            HistoryEntry::new("iced widgets", None),
+        ];
+        let id = app.searches[0].id;
+
+        let _ = app.update(Message::RemoveSearch(id));
+        let _ = app.update(Message::RemoveSearch(id));
+        assert_eq!(queries(app.searches.iter().collect()), ["iced widgets"]);
+    }
+
+    #[test]
+    fn copying_an_entry_keeps_the_history() {
+        let mut app = app();
+        app.searches = vec![HistoryEntry::new("rust lifetimes", None)];
+
+        let _ = app.update(Message::CopySearch(app.searches[0].id));
+        assert_eq!(app.toast.as_deref(), Some("Query copied"));
+        assert_eq!(app.searches.len(), 1);
+    }
+
+    #[test]
+    fn copied_links_follow_the_selected_engine() {
+        let mut app = app();
+        app.searches = vec![HistoryEntry::new("rust lifetimes", None)];
+        let id = app.searches[0].id;
+
+        let _ = app.update(Message::CopyLink(id));
+        assert_eq!(app.toast.as_deref(), Some("DuckDuckGo link copied"));
+
+        let _ = app.update(Message::TagSelected(SearchEngine::StackExchange));
+        let _ = app.update(Message::CopyLink(id));
+        assert_eq!(app.toast.as_deref(), Some("Stack Exchange link copied"));
+    }
+
+    #[test]
+    fn clear_history_shortcut_asks_for_confirmation() {
+        let mut app = app();
+        app.searches = vec![HistoryEntry::new("rust lifetimes", None)];
+
+        let _ = app.update(action_message(Action::ClearHistory));
+        assert_eq!(app.searches.len(), 1);
+        let _ = app.update(action_message(Action::ClearHistory));
+        assert!(app.searches.is_empty());
+    }
+
+    #[test]
+    fn clearing_the_history_asks_for_confirmation() {
+        let mut app = app();
+        app.searches = vec![HistoryEntry::new("rust lifetimes", None)];
+
+        let _ = app.update(Message::RequestClearHistory);
+        assert_eq!(app.searches.len(), 1);
+        let _ = app.update(Message::RequestClearHistory);
+        assert!(app.searches.is_empty());
+
+        app.searches = vec![HistoryEntry::new("rust lifetimes", None)];
+        let _ = app.update(Message::RequestClearHistory);
+        let _ = app.update(Message::QueryChange("rust".into()));
+        let _ = app.update(Message::RequestClearHistory);
+        assert_eq!(app.searches.len(), 1);
+
+        let requested = app.clear_requested.unwrap();
+        let _ = app.update(Message::ClearRequestExpired(requested));
+        assert_eq!(app.clear_requested, None);
+    }
+
+    #[test]
+    fn operator_chips_follow_the_selected_engine() {
+        let mut app = app();
+        let chips = |app: &App| {
+            count(
+                &Tree::new(app.view().as_widget()),
+                Tag::of::<button::State>(),
+            )
+        };
+        let before = chips(&app);
+
+        let _ = app.update(Message::TagSelected(SearchEngine::StackOverflow));
+        assert_eq!(
+            chips(&app),
+            before + SearchEngine::StackOverflow.operators().len()
+        );
+    }
+
+    #[test]
+    fn context_menu_is_one_at_a_time() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("rust lifetimes", None),
+            HistoryEntry::new("iced widgets", None),
+        ];
+        let (first, second) = (app.searches[0].id, app.searches[1].id);
+        let buttons = |app: &App| {
+            count(
+                &Tree::new(app.view().as_widget()),
+                Tag::of::<button::State>(),
+            )
+        };
+        let closed = buttons(&app);
+
+        let _ = app.update(Message::OpenMenu(first));
+        // Copy, re-run, pin, show words and delete.
+        assert_eq!(buttons(&app), closed + 5);
+        let _ = app.update(Message::OpenMenu(second));
+        assert_eq!(app.open_menu, Some(second));
+        assert_eq!(buttons(&app), closed + 5);
+
+        let _ = app.update(Message::Back);
+        assert_eq!(app.open_menu, None);
+
+        let _ = app.update(Message::OpenMenu(first));
+        let _ = app.update(Message::TogglePin(first));
+        assert_eq!(app.open_menu, None);
+        assert!(app.entry(first).unwrap().pinned);
+    }
+
+    #[test]
+    fn short_queries_can_still_be_searched() {
+        let mut app = app();
+        let _ = app.update(Message::QueryChange("?".into()));
+        let _ = app.update(Message::OnPressing);
+        assert_eq!(app.opened_urls.len(), SearchEngine::ALL.len());
+    }
+
+    #[test]
+    fn searches_are_counted_per_engine() {
+        let mut app = app();
+        let _ = app.update(Message::TagSelected(SearchEngine::StackOverflow));
+        let _ = app.update(Message::QueryChange("rust lifetimes".into()));
+        let _ = app.update(Message::OnPressing);
+        assert_eq!(app.engine_searches.get("so"), Some(&1));
+        assert_eq!(app.engine_searches.get("se"), None);
+
+        let _ = app.update(Message::PreferencesChanged(Preferences::default()));
+        assert_eq!(app.preferences.engine_searches, app.engine_searches);
+    }
+
+    #[test]
+    fn searching_can_clear_the_input() {
+        let mut app = app();
+        let _ = app.update(Message::QueryChange("rust lifetimes".into()));
+        let _ = app.update(Message::OnPressing);
+        assert_eq!(app.inputs.query, "rust lifetimes");
+
+        app.preferences.clear_after_search = true;
+        let _ = app.update(Message::QueryChange("iced widgets".into()));
+        let generation = app.suggest_generation;
+        let _ = app.update(Message::OnPressing);
+        assert_eq!(app.inputs.query, "");
+        assert_eq!(app.searches[0].query, "iced widgets");
+        assert!(!app.suggestions_open && app.suggested.is_empty());
+
+        let _ = app.update(Message::RefreshSuggestions(generation));
+        assert!(app.suggested.is_empty());
+    }
+
+    #[test]
+    fn stored_query_matches_the_opened_url() {
+        for trim in [true, false] {
+            let mut app = app();
+            app.preferences.trim_queries = trim;
+            let _ = app.update(Message::TagSelected(SearchEngine::StackOverflow));
+            let _ = app.update(Message::QueryChange("rust   async".into()));
+            let _ = app.update(Message::OnPressing);
+
+            let query = &app.searches[0].query;
+            assert_eq!(query, if trim { "rust async" } else { "rust   async" });
+            assert_eq!(app.opened_urls, [SearchEngine::StackOverflow.url(query)]);
+        }
+    }
+
+    #[test]
+    fn rerunning_loads_the_query() {
+        let mut app = app();
+        app.searches = vec![HistoryEntry::new(
+            "rust lifetimes",
+            Some(SearchEngine::StackOverflow),
+        )];
+
+        let _ = app.update(Message::RerunSearch(app.searches[0].id));
+        assert_eq!(app.inputs.query, "rust lifetimes");
+        assert_eq!(
+            app.opened_urls,
+            [SearchEngine::StackOverflow.url("rust lifetimes")]
+        );
+        assert_eq!(app.searches[0].reruns, 1);
+    }
+
+    #[test]
+    fn repeating_last_reruns_the_newest_search() {
+        let mut app = app();
+        let _ = app.update(Message::RepeatLast);
+        assert!(app.opened_urls.is_empty());
+
+        app.searches = vec![
+            HistoryEntry::new("rust lifetimes", Some(SearchEngine::StackOverflow)),
+            HistoryEntry::new("iced widgets", None),
+        ];
+        let _ = app.update(Message::RepeatLast);
+        assert_eq!(app.inputs.query, "rust lifetimes");
+        assert_eq!(
+            app.opened_urls,
+            [SearchEngine::StackOverflow.url("rust lifetimes")]
+        );
+    }
+
+    #[test]
+    fn arrow_keys_pick_a_history_entry() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("rust lifetimes", Some(SearchEngine::StackOverflow)),
+            HistoryEntry::new("iced widgets", Some(SearchEngine::StackOverflow)),
+        ];
+        let ids = app.visible_entries();
+
+        let _ = app.update(Message::SelectPrevious);
+        assert_eq!(app.selected_history, Some(ids[1]));
+        let _ = app.update(Message::SelectNext);
+        assert_eq!(app.selected_history, Some(ids[0]));
+        let _ = app.update(Message::SelectPrevious);
+        assert_eq!(app.selected_history, Some(ids[1]));
+
+        let _ = app.update(Message::QueryChange("rust".into()));
+        assert_eq!(app.selected_history, None);
+
+        let _ = app.update(Message::SelectNext);
+        let _ = app.update(Message::OnPressing);
+        let entry = app
+            .searches
+            .iter()
+            .find(|entry| entry.id == ids[0])
+            .unwrap();
+        assert_eq!(app.inputs.query, entry.query);
+        assert_eq!(app.selected_history, None);
+    }
+
+    #[test]
+    fn tab_walks_the_focus_ring() {
+        let mut app = app();
+        app.searches = vec![HistoryEntry::new("rust lifetimes", None)];
+        let id = app.searches[0].id;
+        let engines = app.preferences.engines();
+        assert_eq!(app.focused(), Some(Focus::Input));
+
+        let _ = app.update(Message::FocusNext);
+        assert_eq!(app.focused(), Some(Focus::SearchButton));
+        let _ = app.update(Message::FocusNext);
+        assert_eq!(app.focused(), Some(Focus::Tag(engines[0])));
+        let _ = app.update(Message::ActivateFocus);
+        assert_eq!(app.engine(), Some(engines[0]));
+
+        let _ = app.update(Message::FocusPrevious);
+        let _ = app.update(Message::FocusPrevious);
+        let _ = app.update(Message::FocusPrevious);
+        assert_eq!(app.focused(), Some(Focus::History(id)));
+        assert_eq!(app.selected_history, Some(id));
+        let _ = app.update(Message::FocusNext);
+        assert_eq!(app.focused(), Some(Focus::Input));
+    }
+
+    #[test]
+    fn delete_removes_the_highlighted_entry() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("rust lifetimes", None),
+            HistoryEntry::new("iced widgets", None),
+        ];
+        use iced::keyboard::{KeyCode, Modifiers};
+
+        let ids = app.visible_entries();
+        let delete = KeyCombo::new(KeyCode::Delete, Modifiers::empty());
+
+        // Typing on the input keeps the key for itself.
+        let _ = app.update(Message::SelectNext);
+        let _ = app.update(Message::KeyPressed(delete, event::Status::Captured));
+        assert_eq!(app.searches.len(), 2);
+
+        let _ = app.update(Message::KeyPressed(delete, event::Status::Ignored));
+        assert_eq!(app.visible_entries(), [ids[1]]);
+        assert_eq!(app.selected_history, Some(ids[1]));
+
+        let _ = app.update(Message::RemoveSelected);
+        assert!(app.searches.is_empty());
+        assert_eq!(app.selected_history, None);
+        let _ = app.update(Message::RemoveSelected);
+    }
+
+    #[test]
+    fn undo_brings_back_the_removed_entry() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("rust lifetimes", None),
+            HistoryEntry::new("iced widgets", None),
+            HistoryEntry::new("rust traits", None),
+        ];
+
+        let _ = app.update(Message::RemoveSearch(app.searches[1].id));
+        let _ = app.update(Message::UndoRemove);
+        assert_eq!(
+            queries(app.searches.iter().collect()),
+            ["rust lifetimes", "iced widgets", "rust traits"]
+        );
+
+        let _ = app.update(Message::RemoveSearch(app.searches[1].id));
+        let _ = app.update(Message::DismissToast);
+        let _ = app.update(Message::UndoRemove);
+        assert_eq!(app.searches.len(), 2);
+    }
+
+    #[test]
+    fn escape_leaves_the_settings_then_clears_the_query() {
+        let mut app = app();
+        let _ = app.update(Message::QueryChange("rust lifetimes".into()));
+        let _ = app.update(Message::NavigateTo(Screen::Settings));
+
+        let _ = app.update(Message::Back);
+        assert!(app.screen == Screen::Main);
+        assert_eq!(app.inputs.query, "rust lifetimes");
+
+        let _ = app.update(Message::Back);
+        assert!(app.inputs.query.is_empty());
+    }
+
+    #[test]
+    fn history_completes_the_query() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("rust lifetimes", None),
+            HistoryEntry::new("iced widgets", None),
+        ];
+        let _ = app.update(Message::QueryChange("Rus".into()));
+        let _ = app.update(Message::RefreshSuggestions(app.suggest_generation));
+        assert_eq!(app.suggested, ["rust lifetimes"]);
+
+        // Escape closes the completions before clearing the query.
+        let _ = app.update(Message::Back);
+        assert!(!app.suggestions_open);
+        assert_eq!(app.inputs.query, "Rus");
+    }
+
+    #[test]
+    fn stale_suggestions_are_dropped() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("rust lifetimes", None),
+            HistoryEntry::new("iced widgets", None),
+        ];
+        let _ = app.update(Message::QueryChange("Rus".into()));
+        let stale = app.suggest_generation;
+        let _ = app.update(Message::QueryChange("Ice".into()));
+
+        let _ = app.update(Message::RefreshSuggestions(stale));
+        assert!(app.suggested.is_empty());
+
+        let _ = app.update(Message::RefreshSuggestions(app.suggest_generation));
+        assert_eq!(app.suggested, ["iced widgets"]);
+    }
+
+    #[test]
+    fn loading_ends_with_the_last_search() {
+        let mut app = app();
+        app.opening = 2;
+
+        let _ = app.update(Message::BrowserOpened(Ok(())));
+        assert!(app.is_loading());
+        let _ = app.update(Message::BrowserOpened(Ok(())));
+        assert!(!app.is_loading());
+        // Extra completions never wrap the counter around.
+        let _ = app.update(Message::BrowserOpened(Ok(())));
+        assert_eq!(app.opening, 0);
+    }
+
+    #[test]
+    fn engine_groups_collapse() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("rust lifetimes", Some(SearchEngine::StackOverflow)),
+            HistoryEntry::new("iced widgets", None),
+        ];
+        app.preferences.history_group = HistoryGroup::Engine;
+        let buttons = |app: &App| {
+            count(
+                &Tree::new(app.view().as_widget()),
+                Tag::of::<button::State>(),
+            )
+        };
+        let open = buttons(&app);
+
+        let _ = app.update(Message::ToggleGroup(Some(SearchEngine::StackOverflow)));
+        assert!(buttons(&app) < open);
+
+        let _ = app.update(Message::ToggleGroup(Some(SearchEngine::StackOverflow)));
+        assert_eq!(buttons(&app), open);
+    }
+
+    #[test]
+    fn logos_load_after_launch() {
+        let mut app = app();
+        let engine = SearchEngine::StackOverflow;
+        assert!(app.logo(engine).is_none());
+
+        let handle = decode_logo(engine.image().unwrap());
+        assert!(matches!(
+            handle.data(),
+            iced_native::image::Data::Rgba { .. }
+        ));
+        let _ = app.update(Message::LogoLoaded(engine, handle));
+        assert!(app.logo(engine).is_some());
+    }
+
+    #[test]
+    fn history_groups_by_day() {
+        let mut app = app();
+        let now = SystemTime::now();
+        app.searches = vec![
+            HistoryEntry::new("rust lifetimes", None),
+            HistoryEntry::new("iced widgets", None),
+            HistoryEntry::new("serde", None),
+        ];
+        app.searches[0].searched_at = now - Duration::from_secs(86_400);
+        app.searches[1].searched_at = now;
+        app.searches[2].searched_at = now - Duration::from_secs(5 * 86_400);
+        app.preferences.history_group = HistoryGroup::Day;
+
+        assert_eq!(
+            app.history_items(),
+            vec![
+                HistoryItem::Day(history::day(now)),
+                HistoryItem::Entry(1),
+                HistoryItem::Day(history::day(now) - 1),
+                HistoryItem::Entry(0),
+                HistoryItem::Day(history::day(now) - 5),
+                HistoryItem::Entry(2),
+            ]
+        );
+
+        app.history_filter = "rust".into();
+        assert_eq!(
+            app.history_items(),
+            vec![
+                HistoryItem::Day(history::day(now) - 1),
+                HistoryItem::Entry(0)
+            ]
+        );
+    }
+
+    #[test]
+    fn detail_popover_shows_entry_actions() {
+        let mut app = app();
+        app.searches = vec![HistoryEntry::new("rust lifetimes", None)];
+        let closed = count(
+            &Tree::new(app.view().as_widget()),
+            Tag::of::<button::State>(),
+        );
+
+        let _ = app.update(Message::ShowDetail(app.searches[0].id));
+        let open = count(
+            &Tree::new(app.view().as_widget()),
+            Tag::of::<button::State>(),
+        );
+        // Copy, copy link, re-run, edit and delete.
+        assert_eq!(open, closed + 5);
+
+        let _ = app.update(Message::HideDetail);
+        assert_eq!(app.detail, None);
+    }
+
+    #[cfg(feature = "spellcheck")]
+    #[test]
+    fn misspelled_query_offers_a_correction() {
+        let mut app = app();
+        let _ = app.update(Message::QueryChange("instal rust".into()));
+        assert_eq!(app.correction.as_deref(), Some("install rust"));
+
+        let correction = app.correction.clone().unwrap();
+        let _ = app.update(Message::SetSearch(correction));
+        assert_eq!(app.inputs.query, "install rust");
+        assert_eq!(app.correction, None);
+    }
+
+    #[cfg(feature = "previews")]
+    #[test]
+    fn failed_results_offer_a_retry() {
+        let mut app = app();
+        assert_eq!(app.results, ResultState::Idle);
+        let _ = app.update(Message::QueryChange("rust lifetimes".into()));
+        let _ = app.update(Message::OnPressing);
+        assert_eq!(app.results, ResultState::Loading);
+
+        let (query, engine) = app.results_query.clone().unwrap();
+        let _ = app.update(Message::ResultsLoaded(
+            query.clone(),
+            engine,
+            Ok(Vec::new()),
+        ));
+        assert_eq!(app.results, ResultState::Empty);
+        let buttons = |app: &App| {
+            count(
+                &Tree::new(app.view().as_widget()),
+                Tag::of::<button::State>(),
+            )
+        };
+        let empty = buttons(&app);
+
+        let offline = SearchError::Network("dns error".into());
+        let _ = app.update(Message::ResultsLoaded(query.clone(), engine, Err(offline)));
+        assert!(matches!(app.results, ResultState::Error(_)));
+        assert_eq!(buttons(&app), empty + 1);
+
+        assert_eq!(query, "rust lifetimes");
+        let _ = app.update(Message::FetchResults(query, engine));
+        assert_eq!(app.results, ResultState::Loading);
+    }
+
+    #[cfg(feature = "favicons")]
+    #[test]
+    fn custom_engines_fetch_their_favicon() {
+        let mut app = app();
+        let running = app.requests.running();
+        let preferences = Preferences {
+            custom_engines: vec![CustomEngine {
+                name: "crates.io".into(),
+                url: "https://crates.io/search?q={query}".into(),
+                color: None,
+            }],
+            ..Default::default()
+        };
+        let _ = app.update(Message::PreferencesLoaded(Ok(preferences)));
+        assert_eq!(app.requests.running(), running + 1);
+
+        let favicon = image::Handle::from_pixels(1, 1, vec![0; 4]);
+        let _ = app.update(Message::FaviconLoaded("crates.io".into(), Ok(favicon)));
+        assert!(app.favicons.contains_key("crates.io"));
+        assert_eq!(app.requests.running(), running);
+    }
+
+    #[cfg(feature = "previews")]
+    #[test]
+    fn stale_results_are_dropped() {
+        let mut app = app();
+        let _ = app.update(Message::QueryChange("rust traits;; iced".into()));
+        let _ = app.update(Message::OnPressing);
+        assert_eq!(app.results_query, Some(("iced".into(), None)));
+
+        let hit = ResultItem {
+            title: "traits".into(),
+            url: "https://stackoverflow.com/q/1".into(),
+        };
+        let _ = app.update(Message::ResultsLoaded(
+            "rust traits".into(),
+            None,
+            Ok(vec![hit]),
+        ));
+        assert_eq!(app.results, ResultState::Loading);
+
+        let _ = app.update(Message::ResultsLoaded("iced".into(), None, Ok(Vec::new())));
+        assert_eq!(app.results, ResultState::Empty);
+    }
+
+    #[cfg(feature = "previews")]
+    #[test]
+    fn results_wait_for_a_free_request() {
+        let mut app = app();
+        for i in 0..MAX_REQUESTS + 2 {
+            let _ = app.update(Message::FetchResults(format!("query {i}"), None));
+        }
+        assert_eq!(app.requests.running(), MAX_REQUESTS);
+
+        // Only the latest waiting fetch is kept.
+        let _ = app.update(Message::ResultsLoaded(
+            "query 0".into(),
+            None,
+            Ok(Vec::new()),
+        ));
+        assert_eq!(app.requests.running(), MAX_REQUESTS);
+        let _ = app.update(Message::ResultsLoaded(
+            "query 1".into(),
+            None,
+            Ok(Vec::new()),
+        ));
+        assert_eq!(app.requests.running(), MAX_REQUESTS - 1);
+    }
+
+    #[cfg(feature = "previews")]
+    #[test]
+    fn results_respect_the_limit() {
+        let mut app = app();
+        let buttons = |app: &App| {
+            count(
+                &Tree::new(app.view().as_widget()),
+                Tag::of::<button::State>(),
+            )
+        };
+        let none = buttons(&app);
+        let items: Vec<ResultItem> = (0..results::MAX_RESULTS)
+            .map(|i| ResultItem {
+                title: format!("hit {i}"),
+                url: format!("https://stackoverflow.com/q/{i}"),
+            })
+            .collect();
+        let _ = app.update(Message::FetchResults("rust".into(), None));
+        let _ = app.update(Message::ResultsLoaded("rust".into(), None, Ok(items)));
+        assert_eq!(buttons(&app), none + app.preferences.result_limit);
+
+        app.preferences.result_limit = 10;
+        assert_eq!(buttons(&app), none + 10);
+    }
+
+    #[test]
+    fn long_history_only_builds_visible_rows() {
+        let mut app = app();
+        app.searches = (0..1000)
+            .map(|i| HistoryEntry::new(format!("query {i}"), None))
+            .collect();
+        let buttons = |app: &App| {
+            count(
+                &Tree::new(app.view().as_widget()),
+                Tag::of::<button::State>(),
+            )
+        };
+
+        let top = buttons(&app);
+        assert!(top < 100);
+
+        let _ = app.update(Message::HistoryScrolled(scrollable::RelativeOffset {
+            x: 0.0,
+            y: 1.0,
+        }));
+        // Scrolled to the bottom, the same handful of rows is built.
+        assert_eq!(buttons(&app), top);
+    }
+
+    #[test]
+    fn history_changes_are_saved_together() {
+        let mut app = app();
+        let _ = app.update(Message::HistoryLoaded(Ok(Vec::new())));
+        app.searches.push(HistoryEntry::new("rust lifetimes", None));
+        let _ = app.history_changed();
+        let _ = app.update(Message::RemoveSearch(app.searches[0].id));
+        assert_eq!(app.flushed_version, 0);
+
+        let _ = app.update(Message::FlushHistory);
+        assert_eq!(app.flushed_version, app.history_version);
+        assert!(app.is_dirty());
+    }
+
+    fn queries(entries: Vec<&HistoryEntry>) -> Vec<&str> {
+        entries.iter().map(|entry| entry.query.as_str()).collect()
+    }
+
+    #[test]
+    fn clearing_keeps_pinned_entries() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("latest", None),
+            HistoryEntry::new("pinned", None),
+        ];
+        let _ = app.update(Message::TogglePin(app.searches[1].id));
+
+        let _ = app.update(Message::ClearHistory);
+        assert_eq!(queries(app.ranked_history()), vec!["pinned"]);
+    }
+
+    #[test]
+    fn pinned_entries_go_first() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("latest", None),
+            HistoryEntry::new("older", None),
+            HistoryEntry::new("oldest", None),
+        ];
+
+        let _ = app.update(Message::TogglePin(app.searches[2].id));
+        assert_eq!(
+            queries(app.ranked_history()),
+            vec!["oldest", "latest", "older"]
+        );
+
+        let _ = app.update(Message::TogglePin(app.searches[2].id));
+        assert_eq!(
+            queries(app.ranked_history()),
+            vec!["latest", "older", "oldest"]
+        );
+    }
+
+    #[test]
+    fn frequent_queries_move_up() {
+        let mut app = app();
+        app.searches = vec![
+            HistoryEntry::new("latest", None),
+            HistoryEntry::new("older", None),
+            HistoryEntry::new("frequent", None),
+            HistoryEntry::new("oldest", None),
+        ];
+        app.searches[2].reruns = 1;
+
+        // Two places up ties it with the latest, the most used one wins.
+        assert_eq!(
+            queries(app.ranked_history()),
+            vec!["frequent", "latest", "older", "oldest"]
+        );
+    }
+
+    #[test]
+    fn close_waits_for_dirty_history() {
+        let mut app = app();
+        let _ = app.update(Message::HistoryLoaded(Ok(Vec::new())));
+        app.searches.push(HistoryEntry::new("rust lifetimes", None));
+        let _ = app.history_changed();
+        assert!(app.is_dirty());
+
+        let _ = app.update(Message::FinalizeAndExit);
+        assert!(app.exiting);
+        assert!(app.is_dirty());
+
+        let _ = app.update(Message::HistorySaved(app.history_version, Ok(())));
+        assert!(!app.is_dirty());
+    }
+}
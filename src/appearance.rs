@@ -0,0 +1,93 @@
+use std::process::Command;
+
+/// Whether the system asks apps for a light theme, `None` when it can't tell.
+///
+/// Asks `gsettings` on Linux, `defaults` on macOS and the registry on Windows.
+pub fn prefers_light() -> Option<bool> {
+    #[cfg(target_os = "macos")]
+    {
+        // The key only exists while the dark appearance is on.
+        let output = Command::new("defaults")
+            .args(["read", "-g", "AppleInterfaceStyle"])
+            .output()
+            .ok()?;
+        Some(!String::from_utf8_lossy(&output.stdout).contains("Dark"))
+    }
+    #[cfg(windows)]
+    {
+        let output = run(
+            "reg",
+            &[
+                "query",
+                r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize",
+                "/v",
+                "AppsUseLightTheme",
+            ],
+        )?;
+        parse_windows(&output)
+    }
+    #[cfg(not(any(target_os = "macos", windows)))]
+    {
+        let output = run(
+            "gsettings",
+            &["get", "org.gnome.desktop.interface", "color-scheme"],
+        )?;
+        parse_gnome(&output)
+    }
+}
+
+/// Output of a successful `program` run.
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Reads the GNOME `color-scheme` setting.
+///
+/// The `default` scheme is left undecided, most desktops report it without
+/// saying anything about their theme.
+///
+/// # Examples
+///
+/// ```
+/// use search::appearance::parse_gnome;
+///
+/// assert_eq!(parse_gnome("'prefer-dark'\n"), Some(false));
+/// assert_eq!(parse_gnome("'prefer-light'\n"), Some(true));
+/// assert_eq!(parse_gnome("'default'\n"), None);
+/// ```
+pub fn parse_gnome(output: &str) -> Option<bool> {
+    match output.trim().trim_matches('\'') {
+        "prefer-dark" => Some(false),
+        "prefer-light" => Some(true),
+        _ => None,
+    }
+}
+
+/// Reads the `AppsUseLightTheme` value out of a `reg query`.
+///
+/// # Examples
+///
+/// ```
+/// use search::appearance::parse_windows;
+///
+/// let output = "HKEY_CURRENT_USER\\...\\Personalize\n    AppsUseLightTheme    REG_DWORD    0x0\n";
+///
+/// assert_eq!(parse_windows(output), Some(false));
+/// assert_eq!(parse_windows(&output.replace("0x0", "0x1")), Some(true));
+/// assert_eq!(parse_windows("ERROR: not found"), None);
+/// ```
+pub fn parse_windows(output: &str) -> Option<bool> {
+    let line = output
+        .lines()
+        .find(|line| line.trim_start().starts_with("AppsUseLightTheme"))?;
+    match line.split_whitespace().last()? {
+        "0x0" => Some(false),
+        "0x1" => Some(true),
+        _ => None,
+    }
+}
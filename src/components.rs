@@ -1,2 +1,7 @@
-pub mod tags;
+pub mod avatar;
+pub mod badge;
+pub mod modal;
 pub mod option;
+pub mod popover;
+pub mod tags;
+pub mod tooltip;
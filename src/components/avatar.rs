@@ -0,0 +1,27 @@
+use crate::styles::modern::{modern_widget::Container, ModernContainer, RGBColor};
+
+use iced::{
+    alignment::{Horizontal, Vertical},
+    widget::{container, text},
+};
+
+/// Circle with the first letter of `name`, used by engines without a logo.
+///
+/// `size` is the diameter, the letter scales along with it.
+pub fn avatar<'a, Message: 'a>(name: &str, color: RGBColor, size: u16) -> Container<'a, Message> {
+    let initial = name
+        .chars()
+        .find(|c| c.is_alphanumeric())
+        .map_or('?', |c| c.to_ascii_uppercase());
+    container(
+        text(initial)
+            .size(size * 11 / 20)
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .width(size)
+    .height(size)
+    .center_x()
+    .center_y()
+    .style(ModernContainer::Avatar(color))
+}
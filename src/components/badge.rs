@@ -0,0 +1,8 @@
+use crate::styles::modern::{modern_widget::Container, ModernContainer, RGBColor};
+
+use iced::widget::{container, Space};
+
+/// Colored dot used to tell which engine was used, neutral when `color` is `None`.
+pub fn badge<'a, Message: 'a>(color: Option<RGBColor>) -> Container<'a, Message> {
+    container(Space::new(8, 8)).style(ModernContainer::Badge(color))
+}
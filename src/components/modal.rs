@@ -0,0 +1,258 @@
+use iced::widget::{column, container, text};
+use iced_native::{
+    event, keyboard, layout, mouse, overlay, renderer,
+    widget::{self, Tree},
+    Clipboard, Event, Layout, Length, Point, Rectangle, Shell, Size, Widget,
+};
+
+use crate::styles::modern::{
+    modern_widget::{Element, Renderer},
+    ModernContainer, ModernTheme,
+};
+
+/// [`Modal`] shows a card centered over a dimmed `base`, blocking it until dismissed.
+///
+/// Pressing `Escape` or clicking the backdrop emits the dismiss message.
+pub struct Modal<'a, Message> {
+    base: Element<'a, Message>,
+    dialog: Element<'a, Message>,
+    on_dismiss: Message,
+}
+
+/// Creates a [`Modal`] over `base` with a card made of `title` and `content`.
+pub fn modal<'a, Message: 'a>(
+    base: impl Into<Element<'a, Message>>,
+    title: &str,
+    content: impl Into<Element<'a, Message>>,
+    on_dismiss: Message,
+) -> Modal<'a, Message> {
+    let card = container(column![text(title).size(24), content.into()].spacing(15))
+        .padding(25)
+        .max_width(420)
+        .style(ModernContainer::Modal);
+    let dialog = container(card)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x()
+        .center_y()
+        .style(ModernContainer::Backdrop);
+
+    Modal {
+        base: base.into(),
+        dialog: dialog.into(),
+        on_dismiss,
+    }
+}
+
+impl<'a, Message: Clone + 'a> Widget<Message, Renderer> for Modal<'a, Message> {
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.base), Tree::new(&self.dialog)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&[&self.base, &self.dialog])
+    }
+
+    fn width(&self) -> Length {
+        self.base.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.base.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.base.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        self.base
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        self.base.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.base.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &ModernTheme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.base.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        _layout: Layout<'_>,
+        _renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        Some(overlay::Element::new(
+            Point::ORIGIN,
+            Box::new(Overlay {
+                dialog: &mut self.dialog,
+                tree: &mut tree.children[1],
+                on_dismiss: self.on_dismiss.clone(),
+            }),
+        ))
+    }
+}
+
+impl<'a, Message: Clone + 'a> From<Modal<'a, Message>> for Element<'a, Message> {
+    fn from(modal: Modal<'a, Message>) -> Self {
+        Element::new(modal)
+    }
+}
+
+struct Overlay<'a, 'b, Message> {
+    dialog: &'b mut Element<'a, Message>,
+    tree: &'b mut Tree,
+    on_dismiss: Message,
+}
+
+impl<'a, 'b, Message: Clone + 'a> overlay::Overlay<Message, Renderer> for Overlay<'a, 'b, Message> {
+    /// Covers the whole window, the card is centered by the backdrop.
+    fn layout(&self, renderer: &Renderer, bounds: Size, _position: Point) -> layout::Node {
+        let limits = layout::Limits::new(Size::ZERO, bounds)
+            .width(Length::Fill)
+            .height(Length::Fill);
+        self.dialog.as_widget().layout(renderer, &limits)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &ModernTheme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+    ) {
+        self.dialog.as_widget().draw(
+            self.tree,
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            &layout.bounds(),
+        )
+    }
+
+    fn operate(
+        &mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        self.dialog
+            .as_widget()
+            .operate(self.tree, layout, renderer, operation)
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let dismissed = match &event {
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: keyboard::KeyCode::Escape,
+                ..
+            }) => true,
+            Event::Mouse(mouse::Event::ButtonPressed(_)) => !layout
+                .children()
+                .next()
+                .is_some_and(|card| card.bounds().contains(cursor_position)),
+            _ => false,
+        };
+        if dismissed {
+            shell.publish(self.on_dismiss.clone());
+            return event::Status::Captured;
+        }
+
+        self.dialog.as_widget_mut().on_event(
+            self.tree,
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.dialog.as_widget().mouse_interaction(
+            self.tree,
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+}
@@ -0,0 +1,319 @@
+use std::cell::Cell;
+
+use iced_native::{
+    event, layout, mouse, overlay, renderer,
+    widget::{self, tree, Tree},
+    Clipboard, Event, Layout, Length, Point, Rectangle, Shell, Size, Vector, Widget,
+};
+
+use crate::styles::modern::{
+    modern_widget::{Element, Renderer},
+    ModernTheme,
+};
+
+/// Gap between the anchor and the popover.
+const GAP: f32 = 4.0;
+
+/// [`Popover`] shows `content` floating under `base` while it's set.
+///
+/// Clicking outside of both the content and `base` emits the dismiss message, the
+/// click is captured so it doesn't reach the widgets below. Without a dismiss
+/// message those clicks go through.
+pub struct Popover<'a, Message> {
+    base: Element<'a, Message>,
+    content: Option<Element<'a, Message>>,
+    on_dismiss: Option<Message>,
+    at_cursor: bool,
+}
+
+/// Where the base was last right clicked, from its top left corner.
+#[derive(Default)]
+struct State {
+    click: Option<Vector>,
+}
+
+/// Creates a [`Popover`] anchored to `base`, hidden when `content` is `None`.
+pub fn popover<'a, Message>(
+    base: impl Into<Element<'a, Message>>,
+    content: Option<Element<'a, Message>>,
+) -> Popover<'a, Message> {
+    Popover {
+        base: base.into(),
+        content,
+        on_dismiss: None,
+        at_cursor: false,
+    }
+}
+
+impl<'a, Message> Popover<'a, Message> {
+    /// Message emitted when clicking outside of the popover.
+    pub fn on_dismiss(mut self, message: Message) -> Self {
+        self.on_dismiss = Some(message);
+        self
+    }
+
+    /// Opens the popover where the base was last right clicked, like a context menu,
+    /// instead of under the whole base.
+    pub fn at_cursor(mut self) -> Self {
+        self.at_cursor = true;
+        self
+    }
+}
+
+impl<'a, Message: Clone + 'a> Widget<Message, Renderer> for Popover<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        std::iter::once(&self.base)
+            .chain(&self.content)
+            .map(Tree::new)
+            .collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        match &self.content {
+            Some(content) => tree.diff_children(&[&self.base, content]),
+            None => tree.diff_children(std::slice::from_ref(&self.base)),
+        }
+    }
+
+    fn width(&self) -> Length {
+        self.base.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.base.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.base.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        self.base
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let bounds = layout.bounds();
+        if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) = event {
+            if bounds.contains(cursor_position) {
+                tree.state.downcast_mut::<State>().click =
+                    Some(cursor_position - bounds.position());
+            }
+        }
+        self.base.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.base.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &ModernTheme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.base.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        let click = tree.state.downcast_ref::<State>().click;
+        let anchor = match click.filter(|_| self.at_cursor) {
+            Some(click) => Rectangle::new(layout.position() + click, Size::ZERO),
+            None => layout.bounds(),
+        };
+        let (base, content) = tree.children.split_at_mut(1);
+        match (&mut self.content, content.first_mut()) {
+            (Some(content), Some(tree)) => Some(overlay::Element::new(
+                anchor.position(),
+                Box::new(Overlay {
+                    content,
+                    tree,
+                    anchor: Cell::new(anchor),
+                    on_dismiss: self.on_dismiss.clone(),
+                }),
+            )),
+            _ => self
+                .base
+                .as_widget_mut()
+                .overlay(&mut base[0], layout, renderer),
+        }
+    }
+}
+
+impl<'a, Message: Clone + 'a> From<Popover<'a, Message>> for Element<'a, Message> {
+    fn from(popover: Popover<'a, Message>) -> Self {
+        Element::new(popover)
+    }
+}
+
+struct Overlay<'a, 'b, Message> {
+    content: &'b mut Element<'a, Message>,
+    tree: &'b mut Tree,
+    /// Bounds of the base, updated on layout since scrolling moves it.
+    anchor: Cell<Rectangle>,
+    on_dismiss: Option<Message>,
+}
+
+impl<'a, 'b, Message: Clone + 'a> overlay::Overlay<Message, Renderer> for Overlay<'a, 'b, Message> {
+    /// Places the content under the anchor, or over it when there's no room
+    /// left below, keeping it inside of the window.
+    fn layout(&self, renderer: &Renderer, bounds: Size, position: Point) -> layout::Node {
+        let limits = layout::Limits::new(Size::ZERO, bounds)
+            .width(Length::Shrink)
+            .height(Length::Shrink);
+        let mut node = self.content.as_widget().layout(renderer, &limits);
+        let size = node.size();
+        let anchor = self.anchor.get().size();
+        self.anchor.set(Rectangle::new(position, anchor));
+
+        let below = position.y + anchor.height + GAP;
+        let y = if below + size.height <= bounds.height {
+            below
+        } else {
+            (position.y - size.height - GAP).max(0.0)
+        };
+        let x = position.x.min(bounds.width - size.width).max(0.0);
+        node.move_to(Point::new(x, y));
+        node
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &ModernTheme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+    ) {
+        self.content.as_widget().draw(
+            self.tree,
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            &layout.bounds(),
+        )
+    }
+
+    fn operate(
+        &mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(self.tree, layout, renderer, operation)
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        if let (Event::Mouse(mouse::Event::ButtonPressed(_)), Some(message)) =
+            (&event, &self.on_dismiss)
+        {
+            let outside = !layout.bounds().contains(cursor_position)
+                && !self.anchor.get().contains(cursor_position);
+            if outside {
+                shell.publish(message.clone());
+                return event::Status::Captured;
+            }
+        }
+
+        self.content.as_widget_mut().on_event(
+            self.tree,
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            self.tree,
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+}
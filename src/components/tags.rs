@@ -1,15 +1,24 @@
-use std::fs;
-
-use crate::styles::modern::{modern_widget::Button, ModernButton, RGBColor};
+use crate::{
+    engines::SearchEngine,
+    styles::modern::{
+        modern_widget::{Button, Element, MouseArea},
+        ModernButton,
+    },
+};
 
 use iced::{
     alignment::{Horizontal, Vertical},
-    widget::{button, text, Image},
+    widget::{button, mouse_area, text, Image, Space},
     Length,
 };
 use iced_native::image;
 
-pub fn tag<Message>(content: &str, color: RGBColor, message: Message) -> Button<Message> {
+/// Text tag filled with the color of `engine`, or the plain tag color without one.
+pub fn tag<Message>(
+    content: &str,
+    engine: Option<SearchEngine>,
+    message: Message,
+) -> Button<'_, Message> {
     button(
         text(content)
             .size(20)
@@ -17,18 +26,48 @@ pub fn tag<Message>(content: &str, color: RGBColor, message: Message) -> Button<
             .vertical_alignment(Vertical::Center)
             .horizontal_alignment(Horizontal::Center),
     )
-    .style(ModernButton::Tag(color))
+    .style(ModernButton::Tag(engine))
     .padding([5, 10])
     .on_press(message)
 }
 
-pub fn itag<Message>(path: &str, color: RGBColor, message: Message) -> Button<Message> {
-    let file = fs::read(format!("./src/{}", path)).unwrap();
-    let content = Image::new(image::Handle::from_memory(file));
-    button(content.height(30).width(100))
-        .padding([0, 10])
-        .height(30)
-        .width(Length::Shrink)
-        .style(ModernButton::Tag(color))
-        .on_press(message)
+/// Image tag for an engine, filled with its color.
+///
+/// Mouse buttons:
+///
+/// * Left click emits `message`, used to select the engine.
+/// * Middle click emits `on_middle`, used to copy the engine link without searching.
+///
+/// `size` is the height of the tag, the logo and padding scale along with it. Only
+/// the logo height is set, so its width follows the aspect ratio of the image
+/// instead of stretching it.
+/// A `selected` tag is outlined.
+///
+/// While `logo` is still loading the tag shows a block of the engine color in its
+/// place. Engines without a logo show their avatar instead.
+pub fn itag<'a, Message: Clone + 'a>(
+    logo: Option<image::Handle>,
+    engine: SearchEngine,
+    size: u16,
+    selected: bool,
+    message: Message,
+    on_middle: Message,
+) -> MouseArea<'a, Message> {
+    let content: Element<'a, Message> = match logo {
+        Some(logo) => Image::new(logo).height(size).into(),
+        None => Space::new(size * 2, size).into(),
+    };
+    mouse_area(
+        button(content)
+            .padding([0, size / 3])
+            .height(size)
+            .width(Length::Shrink)
+            .style(if selected {
+                ModernButton::SelectedTag(engine)
+            } else {
+                ModernButton::Tag(Some(engine))
+            })
+            .on_press(message),
+    )
+    .on_middle_press(on_middle)
 }
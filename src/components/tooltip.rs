@@ -0,0 +1,24 @@
+use std::borrow::Cow;
+
+use iced::widget::tooltip::Position;
+
+use crate::styles::modern::{
+    modern_widget::{Element, Tooltip},
+    ModernContainer,
+};
+
+/// Wraps `target` so hovering it shows `label` on a [`ModernContainer::Tooltip`]
+/// card, below the target and kept inside the window.
+///
+/// iced shows tooltips as soon as the cursor is over the target, there's no delay
+/// to set.
+pub fn tooltip_helper<'a, Message: 'a>(
+    target: impl Into<Element<'a, Message>>,
+    label: impl Into<Cow<'a, str>>,
+) -> Tooltip<'a, Message> {
+    Tooltip::new(target, label, Position::Bottom)
+        .size(14)
+        .gap(6)
+        .padding(6)
+        .style(ModernContainer::Tooltip)
+}
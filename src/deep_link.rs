@@ -0,0 +1,55 @@
+use crate::engines::{decode, SearchEngine};
+
+/// Scheme registered on the OS to open the app from other programs.
+pub const SCHEME: &str = "capy-search://";
+
+/// [`DeepLink`] is the parsed form of a `capy-search://search?q=...` url.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DeepLink {
+    pub query: String,
+    pub engine: Option<SearchEngine>,
+    pub run: bool,
+}
+
+impl DeepLink {
+    /// Parses a deep link, returning `None` if the url doesn't belong to the app.
+    ///
+    /// Supported parameters:
+    ///
+    /// * `q` - The query that will prefill the input.
+    /// * `engine` - The [`SearchEngine`] id (`so`, `se`) to select.
+    /// * `run` - When `1` or `true`, the search is launched right away.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::deep_link::DeepLink;
+    /// use search::engines::SearchEngine;
+    ///
+    /// let link = DeepLink::parse("capy-search://search?q=rust+lifetimes&engine=so&run=1").unwrap();
+    ///
+    /// assert_eq!(link.query, "rust lifetimes");
+    /// assert_eq!(link.engine, Some(SearchEngine::StackOverflow));
+    /// assert!(link.run);
+    ///
+    /// assert_eq!(DeepLink::parse("https://stackoverflow.com"), None);
+    /// ```
+    pub fn parse(url: &str) -> Option<DeepLink> {
+        let rest = url.strip_prefix(SCHEME)?;
+        let (action, params) = rest.split_once('?').unwrap_or((rest, ""));
+        if action.trim_end_matches('/') != "search" {
+            return None;
+        }
+
+        let mut link = DeepLink::default();
+        for (key, value) in params.split('&').filter_map(|pair| pair.split_once('=')) {
+            match key {
+                "q" => link.query = decode(value),
+                "engine" => link.engine = SearchEngine::from_id(value),
+                "run" => link.run = matches!(value, "1" | "true"),
+                _ => {}
+            }
+        }
+        Some(link)
+    }
+}
@@ -0,0 +1,337 @@
+use crate::styles::modern::{hex, ModernTheme, RGBColor};
+use serde::{Deserialize, Serialize};
+
+/// [`SearchEngine`] are the sites where a query can be sent.
+///
+/// They are serialized with the same id returned by [`SearchEngine::id`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SearchEngine {
+    #[serde(rename = "so")]
+    StackOverflow,
+    #[serde(rename = "se")]
+    StackExchange,
+    /// General web search, for links that aren't tied to a developer site.
+    #[serde(rename = "ddg")]
+    DuckDuckGo,
+}
+
+impl SearchEngine {
+    /// Engines a query is sent to when none is picked.
+    pub const ALL: [SearchEngine; 2] = [SearchEngine::StackOverflow, SearchEngine::StackExchange];
+
+    /// Every engine, the ones with a tag and known by bangs, ids and the config file.
+    pub const KNOWN: [SearchEngine; 3] = [
+        SearchEngine::StackOverflow,
+        SearchEngine::StackExchange,
+        SearchEngine::DuckDuckGo,
+    ];
+
+    /// Short identifier used on deep links and config files.
+    pub fn id(&self) -> &'static str {
+        match self {
+            SearchEngine::StackOverflow => "so",
+            SearchEngine::StackExchange => "se",
+            SearchEngine::DuckDuckGo => "ddg",
+        }
+    }
+
+    /// Human readable name of the engine.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SearchEngine::StackOverflow => "Stack Overflow",
+            SearchEngine::StackExchange => "Stack Exchange",
+            SearchEngine::DuckDuckGo => "DuckDuckGo",
+        }
+    }
+
+    /// Logo shown on the engine tag, embedded in the binary. Engines without one show
+    /// their favicon or avatar instead.
+    ///
+    /// Logos are PNGs scaled down to the tag height. Vector logos would need iced's
+    /// `svg` feature, which isn't enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::engines::SearchEngine;
+    ///
+    /// for engine in SearchEngine::KNOWN {
+    ///     if let Some(image) = engine.image() {
+    ///         assert!(image.starts_with(b"\x89PNG\r\n\x1a\n"));
+    ///     }
+    /// }
+    /// assert_eq!(SearchEngine::DuckDuckGo.image(), None);
+    /// ```
+    pub fn image(&self) -> Option<&'static [u8]> {
+        match self {
+            SearchEngine::StackOverflow => Some(include_bytes!("images/stack-overflow.png")),
+            SearchEngine::StackExchange => Some(include_bytes!("images/stack-exchange.png")),
+            SearchEngine::DuckDuckGo => None,
+        }
+    }
+
+    /// Color of the engine on the given theme, taken from its `[engines]` palette
+    /// and used on the tag and badges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::engines::SearchEngine;
+    /// use search::styles::modern::ModernTheme;
+    ///
+    /// let engine = SearchEngine::StackOverflow;
+    ///
+    /// assert_eq!(engine.color_for(ModernTheme::Dark), (252.0, 187.0, 150.0));
+    /// assert_ne!(engine.color_for(ModernTheme::ColorBlind), engine.color_for(ModernTheme::Dark));
+    /// ```
+    pub fn color_for(&self, theme: ModernTheme) -> RGBColor {
+        let [r, g, b, _] = theme.palette().engines.color(*self).into_rgba8();
+        (r.into(), g.into(), b.into())
+    }
+
+    /// Short tip on the query syntax of the engine, shown under the input.
+    pub fn hint(&self) -> &'static str {
+        match self {
+            SearchEngine::StackOverflow => {
+                "Use [tag] for Stack Overflow tags, is:answer for answers only"
+            }
+            SearchEngine::StackExchange => "Use [tag] for tags, score:3 for well rated posts",
+            SearchEngine::DuckDuckGo => "Use site:docs.rs to search a single site",
+        }
+    }
+
+    /// Search operators offered as quick filters under the input.
+    pub fn operators(&self) -> &'static [&'static str] {
+        match self {
+            SearchEngine::StackOverflow => &["is:question", "is:answer", "hasaccepted:yes"],
+            SearchEngine::StackExchange => &["is:question", "is:answer", "score:3"],
+            SearchEngine::DuckDuckGo => &["site:docs.rs", "site:github.com"],
+        }
+    }
+
+    /// Engine searching a wider range of sites, better suited for long queries.
+    pub fn broader(&self) -> Option<SearchEngine> {
+        match self {
+            SearchEngine::StackOverflow => Some(SearchEngine::StackExchange),
+            SearchEngine::StackExchange | SearchEngine::DuckDuckGo => None,
+        }
+    }
+
+    /// Names accepted as a bang (`!so rust traits`) to route a query to the engine.
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            SearchEngine::StackOverflow => &["so", "stackoverflow"],
+            SearchEngine::StackExchange => &["se", "stackexchange"],
+            SearchEngine::DuckDuckGo => &["ddg", "duckduckgo", "web"],
+        }
+    }
+
+    /// Returns the engine with the given alias, ignoring case.
+    pub fn from_alias(alias: &str) -> Option<SearchEngine> {
+        let alias = alias.to_lowercase();
+        Self::KNOWN
+            .into_iter()
+            .find(|engine| engine.aliases().contains(&alias.as_str()))
+    }
+
+    /// Returns the engine matching the given identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::engines::SearchEngine;
+    ///
+    /// assert_eq!(SearchEngine::from_id("so"), Some(SearchEngine::StackOverflow));
+    /// assert_eq!(SearchEngine::from_id("nope"), None);
+    /// ```
+    pub fn from_id(id: &str) -> Option<SearchEngine> {
+        Self::KNOWN.into_iter().find(|engine| engine.id() == id)
+    }
+
+    fn base_url(&self) -> &'static str {
+        match self {
+            SearchEngine::StackOverflow => "https://stackoverflow.com/search?q=",
+            SearchEngine::StackExchange => "https://stackexchange.com/search?q=",
+            SearchEngine::DuckDuckGo => "https://duckduckgo.com/?q=",
+        }
+    }
+
+    /// Builds the search url for the given query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::engines::SearchEngine;
+    ///
+    /// assert_eq!(
+    ///     SearchEngine::StackOverflow.url("rust traits"),
+    ///     "https://stackoverflow.com/search?q=rust%20traits"
+    /// );
+    /// ```
+    pub fn url(&self, query: &str) -> String {
+        format!("{}{}", self.base_url(), encode(query))
+    }
+}
+
+/// [`CustomEngine`] is a site added on the config file, searched by replacing the
+/// `{query}` of its url.
+///
+/// # Examples
+///
+/// ```
+/// use search::engines::CustomEngine;
+///
+/// let engine: CustomEngine = toml::from_str(
+///     r##"
+///     name = "docs.rs"
+///     url = "https://docs.rs/releases/search?query={query}"
+///     color = "#E57300"
+///     "##,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     engine.url("serde json"),
+///     "https://docs.rs/releases/search?query=serde%20json"
+/// );
+/// assert_eq!(engine.color(), Some((229.0, 115.0, 0.0)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomEngine {
+    /// Text of its tag.
+    pub name: String,
+    /// Search url, with `{query}` where the query goes.
+    pub url: String,
+    /// `#RRGGBB` fill of its tag, the plain tag color without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+impl CustomEngine {
+    /// Builds the search url for the given query.
+    pub fn url(&self, query: &str) -> String {
+        self.url.replace("{query}", &encode(query))
+    }
+
+    /// Color of the tag, `None` when there's none or it isn't a valid hex color.
+    pub fn color(&self) -> Option<RGBColor> {
+        let [r, g, b, _] = hex::from_hex(self.color.as_deref()?)?.into_rgba8();
+        Some((r.into(), g.into(), b.into()))
+    }
+
+    /// Color of its avatar, the tag color of `theme` when it has none.
+    pub fn color_for(&self, theme: ModernTheme) -> RGBColor {
+        self.color().unwrap_or_else(|| {
+            let [r, g, b, _] = theme.palette().buttons.tag().into_rgba8();
+            (r.into(), g.into(), b.into())
+        })
+    }
+}
+
+/// Keeps the custom engines that can be searched, with a warning for each one
+/// left out or whose color is ignored.
+///
+/// # Examples
+///
+/// ```
+/// use search::engines::{custom_engines, CustomEngine};
+///
+/// let engine = |name: &str, url: &str, color: Option<&str>| CustomEngine {
+///     name: name.into(),
+///     url: url.into(),
+///     color: color.map(Into::into),
+/// };
+/// let (engines, warnings) = custom_engines(&[
+///     engine("crates.io", "https://crates.io/search?q={query}", None),
+///     engine("docs", "https://docs.rs", None),
+///     engine("lib.rs", "https://lib.rs/search?q={query}", Some("orange")),
+///     engine(" ", "https://example.com/?q={query}", None),
+/// ]);
+///
+/// assert_eq!(engines.len(), 2);
+/// assert_eq!(warnings.len(), 3);
+/// ```
+pub fn custom_engines(config: &[CustomEngine]) -> (Vec<CustomEngine>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let engines = config
+        .iter()
+        .filter(|engine| {
+            if engine.name.trim().is_empty() {
+                warnings.push(format!("`{}` has no name, skipped", engine.url));
+                false
+            } else if !engine.url.contains("{query}") {
+                warnings.push(format!(
+                    "{}: the url has no `{{query}}` placeholder, skipped",
+                    engine.name
+                ));
+                false
+            } else {
+                if engine.color.is_some() && engine.color().is_none() {
+                    warnings.push(format!("{}: invalid color, ignored", engine.name));
+                }
+                true
+            }
+        })
+        .cloned()
+        .collect();
+    (engines, warnings)
+}
+
+/// Percent-encodes a query so it can be safely placed inside an url.
+///
+/// # Examples
+///
+/// ```
+/// use search::engines::encode;
+///
+/// assert_eq!(encode("c# + c++"), "c%23%20%2B%20c%2B%2B");
+/// assert_eq!(encode("¿qué?"), "%C2%BFqu%C3%A9%3F");
+/// ```
+pub fn encode(query: &str) -> String {
+    query
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Decodes a percent-encoded string, turning `+` into spaces.
+///
+/// Invalid escapes are kept as they are.
+///
+/// # Examples
+///
+/// ```
+/// use search::engines::decode;
+///
+/// assert_eq!(decode("rust+async%20trait"), "rust async trait");
+/// assert_eq!(decode("100%"), "100%");
+/// ```
+pub fn decode(encoded: &str) -> String {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => match encoded
+                .get(i + 1..i + 3)
+                .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 2;
+                }
+                _ => decoded.push(b'%'),
+            },
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
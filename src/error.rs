@@ -0,0 +1,56 @@
+use std::{io, sync::Arc};
+
+use thiserror::Error;
+
+/// [`SearchError`] is what can go wrong reading files, parsing them or reaching the
+/// network.
+///
+/// It's cheap to clone, so it can travel inside the app messages.
+///
+/// # Examples
+///
+/// ```
+/// use search::error::SearchError;
+///
+/// let err = SearchError::parse("config", "expected a boolean");
+///
+/// assert_eq!(err.to_string(), "invalid config: expected a boolean");
+/// assert_eq!(err.exit_code(), 4);
+/// ```
+#[derive(Debug, Clone, Error)]
+pub enum SearchError {
+    #[error("{0}")]
+    Io(Arc<io::Error>),
+    #[error("invalid {what}: {message}")]
+    Parse { what: &'static str, message: String },
+    #[error("network error: {0}")]
+    Network(String),
+}
+
+impl SearchError {
+    pub fn parse(what: &'static str, message: impl ToString) -> Self {
+        SearchError::Parse {
+            what,
+            message: message.to_string(),
+        }
+    }
+
+    /// Exit code of the `--search` command line mode when failing with this error.
+    ///
+    /// `2` is left for wrong usages.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            SearchError::Io(_) => 1,
+            SearchError::Network(_) => 3,
+            SearchError::Parse { .. } => 4,
+        }
+    }
+}
+
+impl From<io::Error> for SearchError {
+    fn from(err: io::Error) -> Self {
+        SearchError::Io(Arc::new(err))
+    }
+}
+
+pub type Result<T> = std::result::Result<T, SearchError>;
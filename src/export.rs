@@ -0,0 +1,113 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::history::HistoryEntry;
+
+/// Writes the history as CSV, with a `timestamp,query,engine` header.
+///
+/// Entries searched on every engine have an empty engine column, and the ones sent to
+/// a custom engine its name.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use search::engines::SearchEngine;
+/// use search::export::history_csv;
+/// use search::history::HistoryEntry;
+///
+/// let mut entry = HistoryEntry::new("rust, \"async\" traits", Some(SearchEngine::StackOverflow));
+/// entry.searched_at = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+///
+/// assert_eq!(
+///     history_csv(&[entry]),
+///     "timestamp,query,engine\r\n2023-11-14T22:13:20Z,\"rust, \"\"async\"\" traits\",so\r\n"
+/// );
+/// ```
+pub fn history_csv(entries: &[HistoryEntry]) -> String {
+    let mut csv = String::from("timestamp,query,engine\r\n");
+    for entry in entries {
+        let row = [
+            timestamp(entry.searched_at),
+            escape(&entry.query),
+            match (&entry.custom, entry.engine) {
+                (Some(custom), _) => escape(custom),
+                (None, engine) => engine.map_or("", |engine| engine.id()).to_string(),
+            },
+        ];
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// Writes the queries of the history, one per line.
+///
+/// Line breaks inside a query are turned into spaces so every line is one search.
+///
+/// # Examples
+///
+/// ```
+/// use search::export::history_text;
+/// use search::history::HistoryEntry;
+///
+/// let entries = [HistoryEntry::new("rust traits", None), HistoryEntry::new("two\nlines", None)];
+///
+/// assert_eq!(history_text(&entries), "rust traits\ntwo lines\n");
+/// assert_eq!(history_text(&[]), "");
+/// ```
+pub fn history_text(entries: &[HistoryEntry]) -> String {
+    entries
+        .iter()
+        .map(|entry| format!("{}\n", entry.query.replace(['\r', '\n'], " ")))
+        .collect()
+}
+
+/// Quotes a CSV field when it contains separators, quotes or line breaks.
+///
+/// # Examples
+///
+/// ```
+/// use search::export::escape;
+///
+/// assert_eq!(escape("plain query"), "plain query");
+/// assert_eq!(escape("a,b"), "\"a,b\"");
+/// assert_eq!(escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+/// assert_eq!(escape("two\nlines"), "\"two\nlines\"");
+/// ```
+pub fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Formats the time as an UTC RFC 3339 timestamp.
+pub fn timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let (days, rest) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_from_days(days as i64);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    )
+}
+
+/// Converts days since the unix epoch into a `(year, month, day)` date.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
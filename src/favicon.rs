@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+
+use crate::{
+    error::{Result, SearchError},
+    storage,
+};
+
+/// Returns the `/favicon.ico` url of the site hosting `url`.
+///
+/// # Examples
+///
+/// ```
+/// use search::favicon::favicon_url;
+///
+/// assert_eq!(
+///     favicon_url("https://stackoverflow.com/search?q=").as_deref(),
+///     Some("https://stackoverflow.com/favicon.ico")
+/// );
+/// assert_eq!(favicon_url("stackoverflow.com"), None);
+/// ```
+pub fn favicon_url(url: &str) -> Option<String> {
+    let (scheme, _) = url.split_once("://")?;
+    Some(format!("{scheme}://{}/favicon.ico", host(url)?))
+}
+
+/// Returns the host of `url`, which names the favicons of the custom engines.
+///
+/// # Examples
+///
+/// ```
+/// use search::favicon::host;
+///
+/// assert_eq!(host("https://docs.rs/releases/search?query={query}"), Some("docs.rs"));
+/// assert_eq!(host("docs.rs"), None);
+/// ```
+pub fn host(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("://")?;
+    rest.split(['/', '?', '#'])
+        .next()
+        .filter(|host| !host.is_empty())
+}
+
+/// File where the favicon of the engine `id` is cached.
+pub fn cache_path(id: &str) -> PathBuf {
+    storage::config_dir()
+        .join("favicons")
+        .join(format!("{id}.ico"))
+}
+
+/// Returns the favicon of the engine `id`, downloading it from the site of
+/// `url` the first time.
+pub async fn load(id: String, url: String) -> Result<Vec<u8>> {
+    let path = cache_path(&id);
+    if let Ok(bytes) = tokio::fs::read(&path).await {
+        return Ok(bytes);
+    }
+
+    let favicon = favicon_url(&url).ok_or_else(|| SearchError::parse("url", &url))?;
+    let network = |err: reqwest::Error| SearchError::Network(err.to_string());
+    let bytes = reqwest::get(&favicon)
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(network)?
+        .bytes()
+        .await
+        .map_err(network)?
+        .to_vec();
+
+    // A failed write only means downloading it again on the next launch.
+    if let Some(parent) = path.parent() {
+        let _ = tokio::fs::create_dir_all(parent).await;
+    }
+    let _ = tokio::fs::write(&path, &bytes).await;
+    Ok(bytes)
+}
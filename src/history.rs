@@ -0,0 +1,611 @@
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    ops::Range,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{engines::SearchEngine, export::civil_from_days, i18n::Strings, query::normalize};
+use serde::{Deserialize, Serialize};
+
+/// Entries kept in the history by default, older ones are dropped.
+pub const MAX_HISTORY: usize = 50;
+
+/// Places an entry moves up on the ranked history for each extra time it was used.
+pub const FREQUENCY_BOOST: usize = 2;
+
+/// [`HistoryEntry`] is a query that was already searched.
+///
+/// Entries are compared by their contents, their [`HistoryEntry::id`] is left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Identifies the entry while the app runs, whatever its position in the list.
+    /// It's not stored, loaded entries get a new one.
+    #[serde(skip, default = "next_id")]
+    pub id: u64,
+    pub query: String,
+    /// Engine the query was sent to, `None` when it went to every engine.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine: Option<SearchEngine>,
+    /// Name of the custom engine the query was sent to, `engine` is `None` then.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom: Option<String>,
+    #[serde(default = "SystemTime::now")]
+    pub searched_at: SystemTime,
+    /// Times the entry was searched again, from the history or typing it again.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub reruns: u32,
+    /// Pinned entries stay at the top of the history.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub pinned: bool,
+}
+
+impl HistoryEntry {
+    pub fn new(query: impl Into<String>, engine: Option<SearchEngine>) -> Self {
+        Self {
+            id: next_id(),
+            query: query.into(),
+            engine,
+            custom: None,
+            searched_at: SystemTime::now(),
+            reruns: 0,
+            pinned: false,
+        }
+    }
+}
+
+/// Adds `entry` on top of `entries`, latest first.
+///
+/// A query already searched with the same engine, ignoring case and accents, moves to
+/// the top instead, keeping its id and pin and counting one more re-run.
+///
+/// # Examples
+///
+/// ```
+/// use search::history::{record, HistoryEntry};
+///
+/// let mut entries = vec![HistoryEntry::new("rust", None), HistoryEntry::new("Iced", None)];
+/// record(&mut entries, HistoryEntry::new("iced", None));
+///
+/// assert_eq!(entries.len(), 2);
+/// assert_eq!(entries[0].query, "iced");
+/// assert_eq!(entries[0].reruns, 1);
+///
+/// record(&mut entries, HistoryEntry::new("Rüst", None));
+/// assert_eq!(entries.len(), 2);
+/// assert_eq!(entries[0].query, "Rüst");
+/// ```
+pub fn record(entries: &mut Vec<HistoryEntry>, entry: HistoryEntry) {
+    let normalized = normalize(&entry.query);
+    let repeated = entries.iter().position(|old| {
+        old.engine == entry.engine
+            && old.custom == entry.custom
+            && normalize(&old.query) == normalized
+    });
+    let entry = match repeated {
+        Some(i) => {
+            let old = entries.remove(i);
+            HistoryEntry {
+                id: old.id,
+                reruns: old.reruns + 1,
+                pinned: old.pinned,
+                ..entry
+            }
+        }
+        None => entry,
+    };
+    entries.insert(0, entry);
+}
+
+/// Drops the oldest entries past `limit`, pinned entries are always kept.
+///
+/// # Examples
+///
+/// ```
+/// use search::history::{trim, HistoryEntry};
+///
+/// let mut oldest = HistoryEntry::new("oldest", None);
+/// oldest.pinned = true;
+/// let mut entries = vec![
+///     HistoryEntry::new("latest", None),
+///     HistoryEntry::new("older", None),
+///     oldest,
+/// ];
+/// trim(&mut entries, 2);
+///
+/// let queries: Vec<&str> = entries.iter().map(|entry| entry.query.as_str()).collect();
+/// assert_eq!(queries, vec!["latest", "oldest"]);
+/// ```
+pub fn trim(entries: &mut Vec<HistoryEntry>, limit: usize) {
+    let mut room = limit.saturating_sub(entries.iter().filter(|entry| entry.pinned).count());
+    entries.retain(|entry| {
+        if entry.pinned {
+            return true;
+        }
+        let kept = room > 0;
+        room = room.saturating_sub(1);
+        kept
+    });
+}
+
+/// How long ago `searched_at` was, from `now`, in its largest unit.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, SystemTime};
+/// use search::history::ago;
+/// use search::i18n::Lang;
+///
+/// let now = SystemTime::now();
+/// let strings = Lang::En.strings();
+///
+/// assert_eq!(ago(now - Duration::from_secs(20), now, strings), "just now");
+/// assert_eq!(ago(now - Duration::from_secs(150), now, strings), "2m ago");
+/// assert_eq!(ago(now - Duration::from_secs(3 * 3600), now, strings), "3h ago");
+/// assert_eq!(ago(now - Duration::from_secs(5 * 86400), now, strings), "5d ago");
+/// assert_eq!(ago(now + Duration::from_secs(60), now, strings), "just now");
+/// assert_eq!(ago(now - Duration::from_secs(150), now, Lang::Es.strings()), "hace 2 min");
+/// ```
+pub fn ago(searched_at: SystemTime, now: SystemTime, strings: &Strings) -> String {
+    let seconds = now
+        .duration_since(searched_at)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let (template, amount) = match seconds {
+        0..=59 => return strings.just_now.to_string(),
+        60..=3599 => (strings.minutes_ago, seconds / 60),
+        3600..=86399 => (strings.hours_ago, seconds / 3600),
+        _ => (strings.days_ago, seconds / 86400),
+    };
+    template.replace("{}", &amount.to_string())
+}
+
+/// Up to `limit` past queries starting with `prefix`, ignoring case and accents,
+/// latest first.
+///
+/// Nothing is suggested for a blank prefix, and the prefix itself isn't.
+///
+/// # Examples
+///
+/// ```
+/// use search::history::{suggest, HistoryEntry};
+///
+/// let entries = vec![
+///     HistoryEntry::new("Rust traits", None),
+///     HistoryEntry::new("iced widgets", None),
+///     HistoryEntry::new("rust lifetimes", None),
+///     HistoryEntry::new("rust traits", None),
+///     HistoryEntry::new("rust", None),
+/// ];
+///
+/// assert_eq!(suggest(&entries, "rust", 5), ["Rust traits", "rust lifetimes"]);
+/// assert_eq!(suggest(&entries, "ícé", 5), ["iced widgets"]);
+/// assert_eq!(suggest(&entries, "rust", 1), ["Rust traits"]);
+/// assert!(suggest(&entries, " ", 5).is_empty());
+/// ```
+pub fn suggest<'a>(entries: &'a [HistoryEntry], prefix: &str, limit: usize) -> Vec<&'a str> {
+    if prefix.trim().is_empty() {
+        return Vec::new();
+    }
+    let prefix = normalize(prefix);
+    let mut suggestions: Vec<&str> = Vec::new();
+    for entry in entries {
+        let query = normalize(&entry.query);
+        if suggestions.len() == limit {
+            break;
+        }
+        if query.starts_with(&prefix)
+            && query != prefix
+            && !suggestions.iter().any(|seen| normalize(seen) == query)
+        {
+            suggestions.push(&entry.query);
+        }
+    }
+    suggestions
+}
+
+/// Whether `query` has every letter of `filter` in the same order, ignoring case,
+/// accents and the spaces of the filter.
+///
+/// # Examples
+///
+/// ```
+/// use search::history::matches;
+///
+/// assert!(matches("Rust lifetimes", "lifetime"));
+/// assert!(matches("Rust lifetimes", "rs lf"));
+/// assert!(matches("Rust lifetimes", ""));
+/// assert!(matches("Café crème", "cafe creme"));
+/// assert!(!matches("Rust lifetimes", "iced"));
+/// ```
+pub fn matches(query: &str, filter: &str) -> bool {
+    let query = normalize(query);
+    let mut query = query.chars();
+    normalize(filter)
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|wanted| query.any(|c| c == wanted))
+}
+
+/// Splits `query` in spans, flagging the letters [`matches`] picks for `filter`.
+///
+/// Letters are compared like [`matches`] does, each one of the filter taking the first
+/// one left on the query. A blank `filter`, or one the query doesn't match, leaves it
+/// in a single span.
+///
+/// # Examples
+///
+/// ```
+/// use search::history::highlight;
+///
+/// assert_eq!(
+///     highlight("Rust lifetimes", "rs lf"),
+///     vec![
+///         ("R", true),
+///         ("u", false),
+///         ("s", true),
+///         ("t ", false),
+///         ("l", true),
+///         ("i", false),
+///         ("f", true),
+///         ("etimes", false),
+///     ]
+/// );
+/// assert_eq!(highlight("Café crème", "cafe"), vec![("Café", true), (" crème", false)]);
+/// assert_eq!(highlight("iced widgets", ""), vec![("iced widgets", false)]);
+/// assert_eq!(highlight("iced widgets", "serde"), vec![("iced widgets", false)]);
+/// ```
+pub fn highlight<'a>(query: &'a str, filter: &str) -> Vec<(&'a str, bool)> {
+    let filter = normalize(filter);
+    let mut wanted = filter.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut letters: Vec<(usize, bool)> = Vec::new();
+    for (at, c) in query.char_indices() {
+        let folded = normalize(c.encode_utf8(&mut [0; 4]));
+        // Combining accents fold into nothing, they go along with their letter.
+        let mut picked = folded.is_empty() && letters.last().is_some_and(|(_, picked)| *picked);
+        for c in folded.chars() {
+            if wanted.next_if_eq(&c).is_some() {
+                picked = true;
+            }
+        }
+        letters.push((at, picked));
+    }
+    if wanted.peek().is_some() || !letters.iter().any(|(_, picked)| *picked) {
+        return vec![(query, false)];
+    }
+
+    let mut spans = Vec::new();
+    let (mut start, mut flagged) = (0, letters[0].1);
+    for &(at, picked) in &letters[1..] {
+        if picked != flagged {
+            spans.push((&query[start..at], flagged));
+            (start, flagged) = (at, picked);
+        }
+    }
+    spans.push((&query[start..], flagged));
+    spans
+}
+
+impl PartialEq for HistoryEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.query == other.query
+            && self.engine == other.engine
+            && self.custom == other.custom
+            && self.searched_at == other.searched_at
+            && self.reruns == other.reruns
+            && self.pinned == other.pinned
+    }
+}
+
+/// A new [`HistoryEntry::id`], never given before.
+fn next_id() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// Times each query was used, counting its searches and re-runs.
+///
+/// # Examples
+///
+/// ```
+/// use search::history::{frequencies, HistoryEntry};
+///
+/// let mut rerun = HistoryEntry::new("iced", None);
+/// rerun.reruns = 2;
+/// let entries = vec![rerun, HistoryEntry::new("rust", None), HistoryEntry::new("iced", None)];
+/// let frequencies = frequencies(&entries);
+///
+/// assert_eq!(frequencies["iced"], 4);
+/// assert_eq!(frequencies["rust"], 1);
+/// ```
+pub fn frequencies(entries: &[HistoryEntry]) -> HashMap<&str, u32> {
+    let mut frequencies = HashMap::new();
+    for entry in entries {
+        *frequencies.entry(entry.query.as_str()).or_default() += 1 + entry.reruns;
+    }
+    frequencies
+}
+
+/// Orders the positions of `entries`, latest first, for the default history list.
+///
+/// Pinned entries go first. The rest keep their recency, but each extra time a
+/// query was used moves it [`FREQUENCY_BOOST`] places up, and the most used one
+/// goes first when two end up on the same place.
+///
+/// # Examples
+///
+/// ```
+/// use search::history::{rank, HistoryEntry};
+///
+/// let mut pinned = HistoryEntry::new("pinned", None);
+/// pinned.pinned = true;
+/// let mut frequent = HistoryEntry::new("frequent", None);
+/// frequent.reruns = 1;
+/// let entries = vec![
+///     HistoryEntry::new("latest", None),
+///     HistoryEntry::new("older", None),
+///     frequent,
+///     pinned,
+/// ];
+///
+/// assert_eq!(rank(&entries), vec![3, 2, 0, 1]);
+/// ```
+pub fn rank(entries: &[HistoryEntry]) -> Vec<usize> {
+    let frequencies = frequencies(entries);
+    let (pinned, recent): (Vec<usize>, Vec<usize>) =
+        (0..entries.len()).partition(|id| entries[*id].pinned);
+
+    let mut recent: Vec<(usize, Reverse<u32>, usize)> = recent
+        .into_iter()
+        .enumerate()
+        .map(|(place, id)| {
+            let frequency = frequencies[entries[id].query.as_str()];
+            let boost = FREQUENCY_BOOST * (frequency as usize - 1);
+            (place.saturating_sub(boost), Reverse(frequency), id)
+        })
+        .collect();
+    recent.sort();
+
+    pinned
+        .into_iter()
+        .chain(recent.into_iter().map(|(_, _, id)| id))
+        .collect()
+}
+
+/// [`HistoryGroup`] is how the history list is organized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryGroup {
+    /// A single list, latest first.
+    #[default]
+    None,
+    /// A section for each engine the entries were sent to.
+    Engine,
+    /// A section for each day with searches, latest first.
+    Day,
+}
+
+/// Groups the positions of `entries` by engine, in the order each engine first shows
+/// up. Entries sent to every engine, or saved before engines were recorded, go last.
+///
+/// # Examples
+///
+/// ```
+/// use search::engines::SearchEngine;
+/// use search::history::{group_by_engine, HistoryEntry};
+///
+/// let entries = vec![
+///     HistoryEntry::new("iced", None),
+///     HistoryEntry::new("lifetimes", Some(SearchEngine::StackExchange)),
+///     HistoryEntry::new("traits", Some(SearchEngine::StackOverflow)),
+///     HistoryEntry::new("borrow", Some(SearchEngine::StackExchange)),
+/// ];
+///
+/// assert_eq!(
+///     group_by_engine(&entries),
+///     vec![
+///         (Some(SearchEngine::StackExchange), vec![1, 3]),
+///         (Some(SearchEngine::StackOverflow), vec![2]),
+///         (None, vec![0]),
+///     ]
+/// );
+/// ```
+pub fn group_by_engine(entries: &[HistoryEntry]) -> Vec<(Option<SearchEngine>, Vec<usize>)> {
+    let mut groups: Vec<(Option<SearchEngine>, Vec<usize>)> = Vec::new();
+    for (id, entry) in entries.iter().enumerate() {
+        match groups
+            .iter_mut()
+            .find(|(engine, _)| *engine == entry.engine)
+        {
+            Some((_, ids)) => ids.push(id),
+            None => groups.push((entry.engine, vec![id])),
+        }
+    }
+    groups.sort_by_key(|(engine, _)| engine.is_none());
+    groups
+}
+
+/// Day `time` falls on in the local timezone, counted in days since the unix epoch.
+///
+/// The offset is the one the OS reports, days go from midnight to midnight in UTC on
+/// platforms where it can't be read.
+pub fn day(time: SystemTime) -> u64 {
+    day_at(time, local_offset(time))
+}
+
+/// Day `time` falls on `offset` seconds east of UTC, see [`day`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use search::history::day_at;
+///
+/// // 01:00 in UTC is still the day before three hours west of it.
+/// let time = UNIX_EPOCH + Duration::from_secs(20_000 * 86_400 + 3_600);
+///
+/// assert_eq!(day_at(time, 0), 20_000);
+/// assert_eq!(day_at(time, -3 * 3_600), 19_999);
+/// assert_eq!(day_at(time, 9 * 3_600), 20_000);
+/// ```
+pub fn day_at(time: SystemTime, offset: i64) -> u64 {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64);
+    u64::try_from((seconds + offset).div_euclid(86_400)).unwrap_or_default()
+}
+
+/// Seconds the local timezone is ahead of UTC at `time`, as the OS reports it.
+#[cfg(unix)]
+fn local_offset(time: SystemTime) -> i64 {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs()) as libc::time_t;
+    // SAFETY: `localtime_r` only writes to `tm`, which lives through the call, and an
+    // all zero `tm` is a valid value of the plain C struct.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    let local = unsafe { libc::localtime_r(&seconds, &mut tm) };
+    if local.is_null() {
+        0
+    } else {
+        tm.tm_gmtoff as i64
+    }
+}
+
+/// Seconds the local timezone is ahead of UTC, unknown outside of unix.
+#[cfg(not(unix))]
+fn local_offset(_time: SystemTime) -> i64 {
+    0
+}
+
+/// Groups the positions of `entries` by the [`day`] they were searched on, latest
+/// day first and latest entry first within each day.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, SystemTime};
+/// use search::history::{day, group_by_day, HistoryEntry};
+///
+/// let now = SystemTime::now();
+/// let mut entries = vec![
+///     HistoryEntry::new("iced", None),
+///     HistoryEntry::new("lifetimes", None),
+///     HistoryEntry::new("traits", None),
+/// ];
+/// entries[0].searched_at = now - Duration::from_secs(2 * 86_400);
+/// entries[1].searched_at = now - Duration::from_secs(60);
+/// entries[2].searched_at = now;
+///
+/// assert_eq!(
+///     group_by_day(&entries),
+///     vec![(day(now), vec![2, 1]), (day(now) - 2, vec![0])]
+/// );
+/// ```
+pub fn group_by_day(entries: &[HistoryEntry]) -> Vec<(u64, Vec<usize>)> {
+    let mut ids: Vec<usize> = (0..entries.len()).collect();
+    ids.sort_by_key(|id| Reverse(entries[*id].searched_at));
+    let mut groups: Vec<(u64, Vec<usize>)> = Vec::new();
+    for id in ids {
+        let day = day(entries[id].searched_at);
+        match groups.last_mut() {
+            Some((last, ids)) if *last == day => ids.push(id),
+            _ => groups.push((day, vec![id])),
+        }
+    }
+    groups
+}
+
+/// Header of the section of `day`, "Today" and "Yesterday" or its date otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use search::history::day_label;
+/// use search::i18n::Lang;
+///
+/// let strings = Lang::En.strings();
+///
+/// assert_eq!(day_label(20_000, 20_000, strings), "Today");
+/// assert_eq!(day_label(19_999, 20_000, strings), "Yesterday");
+/// assert_eq!(day_label(19_990, 20_000, strings), "2024-09-24");
+/// assert_eq!(day_label(19_999, 20_000, Lang::Es.strings()), "Ayer");
+/// ```
+pub fn day_label(day: u64, today: u64, strings: &Strings) -> String {
+    match today.checked_sub(day) {
+        Some(0) => strings.today.into(),
+        Some(1) => strings.yesterday.into(),
+        _ => {
+            let (year, month, day) = civil_from_days(day as i64);
+            format!("{year:04}-{month:02}-{day:02}")
+        }
+    }
+}
+
+/// [`Window`] is the part of a scrolled list worth building, with the space taken by
+/// the rows left out above and below it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Window {
+    pub before: f32,
+    pub rows: Range<usize>,
+    pub after: f32,
+}
+
+/// Finds the rows of `heights` within `viewport` pixels from the scroll `offset`,
+/// plus `buffer` pixels on each side so fast scrolling doesn't show gaps.
+///
+/// # Examples
+///
+/// ```
+/// use search::history::{window, Window};
+///
+/// let heights = [40.0; 1000];
+///
+/// assert_eq!(
+///     window(&heights, 2000.0, 200.0, 80.0),
+///     Window { before: 1920.0, rows: 48..57, after: 37720.0 }
+/// );
+/// assert_eq!(
+///     window(&heights[..3], 0.0, 200.0, 80.0),
+///     Window { before: 0.0, rows: 0..3, after: 0.0 }
+/// );
+/// ```
+pub fn window(heights: &[f32], offset: f32, viewport: f32, buffer: f32) -> Window {
+    let start = offset - buffer;
+    let end = offset + viewport + buffer;
+
+    let mut top = 0.0;
+    let mut before = 0.0;
+    let mut first = heights.len();
+    let mut last = heights.len();
+    for (id, height) in heights.iter().enumerate() {
+        let bottom = top + height;
+        if first == heights.len() && bottom > start {
+            first = id;
+            before = top;
+        }
+        if top >= end {
+            last = id;
+            break;
+        }
+        top = bottom;
+    }
+    if first == heights.len() {
+        before = top;
+    }
+
+    Window {
+        before,
+        rows: first..last,
+        after: heights[last..].iter().sum(),
+    }
+}
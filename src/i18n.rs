@@ -0,0 +1,466 @@
+use serde::{Deserialize, Serialize};
+
+/// [`Lang`] is a language the interface can be shown in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    #[default]
+    En,
+    Es,
+}
+
+/// [`Strings`] are the texts of the interface in a single language.
+#[derive(Debug)]
+pub struct Strings {
+    pub subtitle: &'static str,
+    /// Warning under the input while the query is too short to be useful.
+    pub query_too_short: &'static str,
+    /// Hints of the query input, one is picked on launch.
+    pub placeholders: [&'static str; 3],
+    pub history: &'static str,
+    pub filter: &'static str,
+    /// Button re-running the latest search of the history.
+    pub repeat_last: &'static str,
+    pub export: &'static str,
+    pub clear_all: &'static str,
+    /// Label of "Clear all" while it waits for a second click.
+    pub confirm_clear: &'static str,
+    pub empty_history: &'static str,
+    pub no_matches: &'static str,
+    /// Button shown while the query is a url.
+    pub open_link: &'static str,
+    /// Suggestion of the closest known bang, `{}` stands for it like `!so`.
+    pub did_you_mean: &'static str,
+    /// Suggestion of a broader engine for long queries.
+    pub long_query: &'static str,
+    /// Suggestion of the spelling correction of the query.
+    pub spelling: &'static str,
+    pub light_mode: &'static str,
+    pub undo: &'static str,
+    pub search_removed: &'static str,
+    pub query_copied: &'static str,
+    /// Toast after copying the url of the query on an engine.
+    pub link_copied: &'static str,
+    /// Titles splitting the pinned searches from the rest.
+    pub pinned: &'static str,
+    pub recent: &'static str,
+    pub copy: &'static str,
+    pub copy_link: &'static str,
+    pub rerun: &'static str,
+    pub edit: &'static str,
+    pub delete: &'static str,
+    pub pin: &'static str,
+    pub unpin: &'static str,
+    pub show_words: &'static str,
+    pub hide_words: &'static str,
+    /// Engine of a search that went to every engine, on its details.
+    pub every_engine: &'static str,
+    pub engine: &'static str,
+    pub searched_at: &'static str,
+    pub reruns: &'static str,
+    /// Header of the section of the searches without an engine.
+    pub all_engines: &'static str,
+    pub restore_title: &'static str,
+    pub restore: &'static str,
+    pub dismiss: &'static str,
+    /// Title of the confirmation before opening many tabs.
+    pub open_tabs: &'static str,
+    pub open_tabs_notice: &'static str,
+    pub open: &'static str,
+    pub cancel: &'static str,
+    /// Notice of a full history, with `{len}` searches out of `{limit}`.
+    pub history_full: &'static str,
+    pub increase_limit: &'static str,
+    /// Singular and plural of the searches counted under the history.
+    pub searches: [&'static str; 2],
+    /// Count of the searches left by the filter, `{shown}` out of `{total}`.
+    pub shown_of: &'static str,
+    pub loading_results: &'static str,
+    pub no_results: &'static str,
+    pub retry: &'static str,
+    /// Count of the listed results, `{shown}` out of `{total}` fetched.
+    pub showing_results: &'static str,
+    /// Toasts of the failed tasks, `{}` stands for the error.
+    pub browser_failed: &'static str,
+    pub export_failed: &'static str,
+    pub history_load_failed: &'static str,
+    pub history_save_failed: &'static str,
+    pub last_query_failed: &'static str,
+    pub preferences_load_failed: &'static str,
+    pub preferences_save_failed: &'static str,
+    pub theme_load_failed: &'static str,
+    pub results_failed: &'static str,
+    pub results_offline: &'static str,
+    /// Toast after exporting, `{}` stands for the written file.
+    pub exported: &'static str,
+    /// How long ago an entry was searched, `{}` stands for the amount.
+    pub just_now: &'static str,
+    pub minutes_ago: &'static str,
+    pub hours_ago: &'static str,
+    pub days_ago: &'static str,
+    /// Headers of the history grouped by day.
+    pub today: &'static str,
+    pub yesterday: &'static str,
+    /// Title of the settings screen.
+    pub settings: &'static str,
+    pub trim_queries: &'static str,
+    pub sanitize_input: &'static str,
+    pub clear_on_engine_switch: &'static str,
+    pub clear_after_search: &'static str,
+    pub dedupe_opens: &'static str,
+    pub background_open: &'static str,
+    pub open_urls_directly: &'static str,
+    pub restore_last_query: &'static str,
+    pub compact_rows: &'static str,
+    pub close_suggestions: &'static str,
+    pub long_query_hint: &'static str,
+    pub live_clipboard: &'static str,
+    /// Warning under the live clipboard toggle.
+    pub live_clipboard_note: &'static str,
+    pub sound_feedback: &'static str,
+    pub engine_order: &'static str,
+    pub tag_size: &'static str,
+    pub small: &'static str,
+    pub medium: &'static str,
+    pub large: &'static str,
+    pub theme: &'static str,
+    pub accent: &'static str,
+    /// Accent option keeping the one of the theme.
+    pub theme_accent: &'static str,
+    pub blue: &'static str,
+    pub green: &'static str,
+    pub pink: &'static str,
+    pub purple: &'static str,
+    pub default_engine: &'static str,
+    /// Default engine option searching every engine.
+    pub all: &'static str,
+    pub language: &'static str,
+    /// Language option following the system.
+    pub system: &'static str,
+    pub text_size: &'static str,
+    pub open_in: &'static str,
+    pub new_tab: &'static str,
+    pub front_tab: &'static str,
+    pub density: &'static str,
+    pub comfortable: &'static str,
+    pub compact: &'static str,
+    pub history_limit: &'static str,
+    pub group_history: &'static str,
+    pub group_none: &'static str,
+    pub by_engine: &'static str,
+    pub by_day: &'static str,
+    pub tab_threshold: &'static str,
+    /// Tab threshold option, `{}` stands for the tabs.
+    pub tabs: &'static str,
+    pub results_listed: &'static str,
+    pub searches_per_engine: &'static str,
+    pub window: &'static str,
+    pub reset: &'static str,
+    pub export_theme: &'static str,
+    pub export_results: &'static str,
+    pub back: &'static str,
+}
+
+const ENGLISH: Strings = Strings {
+    subtitle: "Programmer search engine",
+    query_too_short: "Query too short",
+    placeholders: [
+        "Search anything...",
+        "Give me your question...",
+        "Let's step on those errors...",
+    ],
+    history: "History",
+    filter: "Filter",
+    repeat_last: "Repeat last",
+    export: "Export",
+    clear_all: "Clear all",
+    confirm_clear: "Sure?",
+    empty_history: "You haven't searched anything yet...",
+    no_matches: "No searches match the filter",
+    open_link: "Open link?",
+    did_you_mean: "Did you mean {}?",
+    long_query: "Long query \u{2014} try {}?",
+    spelling: "Did you mean: {}?",
+    light_mode: "Light mode",
+    undo: "Undo",
+    search_removed: "Search removed",
+    query_copied: "Query copied",
+    link_copied: "{} link copied",
+    pinned: "Pinned",
+    recent: "Recent",
+    copy: "Copy",
+    copy_link: "Copy link",
+    rerun: "Re-run",
+    edit: "Edit",
+    delete: "Delete",
+    pin: "Pin",
+    unpin: "Unpin",
+    show_words: "Show words",
+    hide_words: "Hide words",
+    every_engine: "Every engine",
+    engine: "Engine: {}",
+    searched_at: "Searched at: {}",
+    reruns: "Re-run {} times",
+    all_engines: "All engines",
+    restore_title: "Restore your last query?",
+    restore: "Restore",
+    dismiss: "Dismiss",
+    open_tabs: "Open {} tabs?",
+    open_tabs_notice: "Every query gets its own tab on each engine it's searched on.",
+    open: "Open",
+    cancel: "Cancel",
+    history_full: "{len}/{limit} \u{2014} oldest will be removed",
+    increase_limit: "Increase limit",
+    searches: ["search", "searches"],
+    shown_of: "{shown} of {total}",
+    loading_results: "Loading results...",
+    no_results: "No results for this search",
+    retry: "Retry",
+    showing_results: "Showing {shown} of {total}",
+    browser_failed: "Couldn't open the browser: {}",
+    export_failed: "Couldn't export: {}",
+    history_load_failed: "Couldn't load the history: {}",
+    history_save_failed: "Couldn't save the history: {}",
+    last_query_failed: "Couldn't clear the last query: {}",
+    preferences_load_failed: "Couldn't load the preferences: {}",
+    preferences_save_failed: "Couldn't save the preferences: {}",
+    theme_load_failed: "Couldn't load the theme: {}",
+    results_failed: "Couldn't load the results: {}",
+    results_offline: "Couldn't reach the results, check your connection",
+    exported: "Exported to {}",
+    just_now: "just now",
+    minutes_ago: "{}m ago",
+    hours_ago: "{}h ago",
+    days_ago: "{}d ago",
+    today: "Today",
+    yesterday: "Yesterday",
+    settings: "Settings",
+    trim_queries: "Trim queries",
+    sanitize_input: "Strip terminal colors and control characters",
+    clear_on_engine_switch: "Clear the input when switching engines",
+    clear_after_search: "Clear the input after searching",
+    dedupe_opens: "Don't reopen a search opened moments ago",
+    background_open: "Open searches in the background (macOS)",
+    open_urls_directly: "Open links instead of searching them",
+    restore_last_query: "Offer to restore the last query on launch",
+    compact_rows: "Compact history rows",
+    close_suggestions: "Close the suggestions when clicking outside",
+    long_query_hint: "Suggest a broader engine for long queries",
+    live_clipboard: "Copy the query to the clipboard while typing",
+    live_clipboard_note: "Replaces whatever the clipboard holds on every keystroke",
+    sound_feedback: "Beep when searching an empty query",
+    engine_order: "Engine order",
+    tag_size: "Tag size",
+    small: "Small",
+    medium: "Medium",
+    large: "Large",
+    theme: "Theme",
+    accent: "Accent",
+    theme_accent: "Theme",
+    blue: "Blue",
+    green: "Green",
+    pink: "Pink",
+    purple: "Purple",
+    default_engine: "Default engine",
+    all: "All",
+    language: "Language",
+    system: "System",
+    text_size: "Text size",
+    open_in: "Open searches in",
+    new_tab: "New tab",
+    front_tab: "Front tab",
+    density: "Density",
+    comfortable: "Comfortable",
+    compact: "Compact",
+    history_limit: "History limit",
+    group_history: "Group history",
+    group_none: "None",
+    by_engine: "By engine",
+    by_day: "By day",
+    tab_threshold: "Ask before opening more than",
+    tabs: "{} tabs",
+    results_listed: "Results listed",
+    searches_per_engine: "Searches per engine",
+    window: "Window size and position",
+    reset: "Reset",
+    export_theme: "Export theme",
+    export_results: "Export results (CSV)",
+    back: "Back",
+};
+
+const SPANISH: Strings = Strings {
+    subtitle: "Buscador para programadores",
+    query_too_short: "La búsqueda es muy corta",
+    placeholders: [
+        "Buscá lo que quieras...",
+        "Hacé tu pregunta...",
+        "Aplastemos esos errores...",
+    ],
+    history: "Historial",
+    filter: "Filtrar",
+    repeat_last: "Repetir última",
+    export: "Exportar",
+    clear_all: "Borrar todo",
+    confirm_clear: "¿Seguro?",
+    empty_history: "Todavía no buscaste nada...",
+    no_matches: "Ninguna búsqueda coincide con el filtro",
+    open_link: "¿Abrir el enlace?",
+    did_you_mean: "¿Quisiste decir {}?",
+    long_query: "Búsqueda larga \u{2014} ¿probás {}?",
+    spelling: "¿Quisiste decir: {}?",
+    light_mode: "Modo claro",
+    undo: "Deshacer",
+    search_removed: "Búsqueda borrada",
+    query_copied: "Búsqueda copiada",
+    link_copied: "Enlace de {} copiado",
+    pinned: "Fijadas",
+    recent: "Recientes",
+    copy: "Copiar",
+    copy_link: "Copiar enlace",
+    rerun: "Repetir",
+    edit: "Editar",
+    delete: "Borrar",
+    pin: "Fijar",
+    unpin: "Desfijar",
+    show_words: "Mostrar palabras",
+    hide_words: "Ocultar palabras",
+    every_engine: "Todos los buscadores",
+    engine: "Buscador: {}",
+    searched_at: "Buscada el: {}",
+    reruns: "Repetida {} veces",
+    all_engines: "Todos los buscadores",
+    restore_title: "¿Recuperar tu última búsqueda?",
+    restore: "Recuperar",
+    dismiss: "Descartar",
+    open_tabs: "¿Abrir {} pestañas?",
+    open_tabs_notice: "Cada búsqueda abre su propia pestaña en cada buscador donde la buscás.",
+    open: "Abrir",
+    cancel: "Cancelar",
+    history_full: "{len}/{limit} \u{2014} se van a borrar las más viejas",
+    increase_limit: "Aumentar el límite",
+    searches: ["búsqueda", "búsquedas"],
+    shown_of: "{shown} de {total}",
+    loading_results: "Cargando resultados...",
+    no_results: "No hay resultados para esta búsqueda",
+    retry: "Reintentar",
+    showing_results: "Mostrando {shown} de {total}",
+    browser_failed: "No se pudo abrir el navegador: {}",
+    export_failed: "No se pudo exportar: {}",
+    history_load_failed: "No se pudo cargar el historial: {}",
+    history_save_failed: "No se pudo guardar el historial: {}",
+    last_query_failed: "No se pudo borrar la última búsqueda: {}",
+    preferences_load_failed: "No se pudieron cargar las preferencias: {}",
+    preferences_save_failed: "No se pudieron guardar las preferencias: {}",
+    theme_load_failed: "No se pudo cargar el tema: {}",
+    results_failed: "No se pudieron cargar los resultados: {}",
+    results_offline: "No se pudo llegar a los resultados, revisá tu conexión",
+    exported: "Exportado en {}",
+    just_now: "recién",
+    minutes_ago: "hace {} min",
+    hours_ago: "hace {} h",
+    days_ago: "hace {} d",
+    today: "Hoy",
+    yesterday: "Ayer",
+    settings: "Ajustes",
+    trim_queries: "Recortar las búsquedas",
+    sanitize_input: "Quitar los colores de terminal y los caracteres de control",
+    clear_on_engine_switch: "Vaciar la entrada al cambiar de buscador",
+    clear_after_search: "Vaciar la entrada después de buscar",
+    dedupe_opens: "No reabrir una búsqueda abierta hace un momento",
+    background_open: "Abrir las búsquedas en segundo plano (macOS)",
+    open_urls_directly: "Abrir los enlaces en vez de buscarlos",
+    restore_last_query: "Ofrecer recuperar la última búsqueda al abrir",
+    compact_rows: "Filas compactas en el historial",
+    close_suggestions: "Cerrar las sugerencias al hacer clic afuera",
+    long_query_hint: "Sugerir un buscador más amplio para búsquedas largas",
+    live_clipboard: "Copiar la búsqueda al portapapeles mientras escribís",
+    live_clipboard_note: "Reemplaza lo que tenga el portapapeles con cada tecla",
+    sound_feedback: "Pitar al buscar una búsqueda vacía",
+    engine_order: "Orden de los buscadores",
+    tag_size: "Tamaño de las etiquetas",
+    small: "Chico",
+    medium: "Mediano",
+    large: "Grande",
+    theme: "Tema",
+    accent: "Acento",
+    theme_accent: "Del tema",
+    blue: "Azul",
+    green: "Verde",
+    pink: "Rosa",
+    purple: "Violeta",
+    default_engine: "Buscador por defecto",
+    all: "Todos",
+    language: "Idioma",
+    system: "Sistema",
+    text_size: "Tamaño del texto",
+    open_in: "Abrir las búsquedas en",
+    new_tab: "Pestaña nueva",
+    front_tab: "Pestaña actual",
+    density: "Densidad",
+    comfortable: "Cómoda",
+    compact: "Compacta",
+    history_limit: "Límite del historial",
+    group_history: "Agrupar el historial",
+    group_none: "No",
+    by_engine: "Por buscador",
+    by_day: "Por día",
+    tab_threshold: "Preguntar antes de abrir más de",
+    tabs: "{} pestañas",
+    results_listed: "Resultados listados",
+    searches_per_engine: "Búsquedas por buscador",
+    window: "Tamaño y posición de la ventana",
+    reset: "Restablecer",
+    export_theme: "Exportar el tema",
+    export_results: "Exportar los resultados (CSV)",
+    back: "Volver",
+};
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::En, Lang::Es];
+
+    /// Name of the language, written in itself.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lang::En => "English",
+            Lang::Es => "Español",
+        }
+    }
+
+    pub fn strings(&self) -> &'static Strings {
+        match self {
+            Lang::En => &ENGLISH,
+            Lang::Es => &SPANISH,
+        }
+    }
+
+    /// Language of a POSIX locale like `es_AR.UTF-8`, `None` when it isn't translated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::i18n::Lang;
+    ///
+    /// assert_eq!(Lang::from_locale("es_AR.UTF-8"), Some(Lang::Es));
+    /// assert_eq!(Lang::from_locale("en-US"), Some(Lang::En));
+    /// assert_eq!(Lang::from_locale("C"), None);
+    /// assert_eq!(Lang::from_locale("fr_FR"), None);
+    /// ```
+    pub fn from_locale(locale: &str) -> Option<Lang> {
+        let code = locale.split(['_', '-', '.', '@']).next()?;
+        match code.to_ascii_lowercase().as_str() {
+            "en" => Some(Lang::En),
+            "es" => Some(Lang::Es),
+            _ => None,
+        }
+    }
+
+    /// Language of the system locale, English when it isn't translated.
+    pub fn system() -> Lang {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|locale| !locale.is_empty())
+            .and_then(|locale| Lang::from_locale(&locale))
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,386 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+use iced_native::keyboard::{KeyCode, Modifiers};
+
+/// [`Action`] is something a keyboard shortcut can trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Submit,
+    ToggleTheme,
+    FocusInput,
+    ClearHistory,
+    OpenSettings,
+    Back,
+    CycleEngine,
+    ClearQuery,
+    SelectPrevious,
+    SelectNext,
+    RepeatLast,
+    RemoveSelected,
+    FocusNext,
+    FocusPrevious,
+}
+
+impl Action {
+    pub const ALL: [Action; 14] = [
+        Action::Submit,
+        Action::ToggleTheme,
+        Action::FocusInput,
+        Action::ClearHistory,
+        Action::OpenSettings,
+        Action::Back,
+        Action::CycleEngine,
+        Action::ClearQuery,
+        Action::SelectPrevious,
+        Action::SelectNext,
+        Action::RepeatLast,
+        Action::RemoveSelected,
+        Action::FocusNext,
+        Action::FocusPrevious,
+    ];
+
+    /// Name of the action on the `keybindings` config.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Submit => "submit",
+            Action::ToggleTheme => "toggle_theme",
+            Action::FocusInput => "focus_input",
+            Action::ClearHistory => "clear_history",
+            Action::OpenSettings => "open_settings",
+            Action::Back => "back",
+            Action::CycleEngine => "cycle_engine",
+            Action::ClearQuery => "clear_query",
+            Action::SelectPrevious => "select_previous",
+            Action::SelectNext => "select_next",
+            Action::RepeatLast => "repeat_last",
+            Action::RemoveSelected => "remove_selected",
+            Action::FocusNext => "focus_next",
+            Action::FocusPrevious => "focus_previous",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| action.name() == name)
+    }
+
+    /// Combo bound to the action when the config doesn't set one, `None` leaves it
+    /// unbound.
+    pub fn default_combo(&self) -> Option<&'static str> {
+        match self {
+            Action::Submit => Some("enter"),
+            Action::ToggleTheme => Some("cmd+t"),
+            Action::FocusInput => Some("cmd+l"),
+            Action::ClearHistory => None,
+            Action::OpenSettings => Some("cmd+,"),
+            Action::Back => Some("escape"),
+            Action::CycleEngine => Some("e"),
+            Action::ClearQuery => Some("cmd+backspace"),
+            Action::SelectPrevious => Some("up"),
+            Action::SelectNext => Some("down"),
+            Action::RepeatLast => Some("cmd+r"),
+            Action::RemoveSelected => Some("delete"),
+            Action::FocusNext => Some("tab"),
+            Action::FocusPrevious => Some("shift+tab"),
+        }
+    }
+}
+
+/// Default `keybindings` config, every action with its default combo.
+pub fn default_keybindings() -> BTreeMap<String, String> {
+    Action::ALL
+        .iter()
+        .filter_map(|action| Some((action.name().to_string(), action.default_combo()?.into())))
+        .collect()
+}
+
+/// [`KeyCombo`] is a key along with the modifiers held while pressing it.
+///
+/// Combos are written as the modifiers followed by the key, joined with `+`. `cmd`
+/// stands for `logo` on macOS and `ctrl` elsewhere.
+///
+/// # Examples
+///
+/// ```
+/// use iced_native::keyboard::{KeyCode, Modifiers};
+/// use search::keybindings::KeyCombo;
+///
+/// assert_eq!(
+///     "ctrl+shift+t".parse(),
+///     Ok(KeyCombo::new(KeyCode::T, Modifiers::CTRL | Modifiers::SHIFT))
+/// );
+/// assert_eq!("Escape".parse(), Ok(KeyCombo::new(KeyCode::Escape, Modifiers::empty())));
+/// assert_eq!("cmd+,".parse(), Ok(KeyCombo::new(KeyCode::Comma, Modifiers::COMMAND)));
+/// assert_eq!("alt + f4".parse(), Ok(KeyCombo::new(KeyCode::F4, Modifiers::ALT)));
+/// assert!("ctrl+".parse::<KeyCombo>().is_err());
+/// assert!("hyper+a".parse::<KeyCombo>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    pub key: KeyCode,
+    pub modifiers: Modifiers,
+}
+
+impl KeyCombo {
+    pub fn new(key: KeyCode, modifiers: Modifiers) -> Self {
+        Self { key, modifiers }
+    }
+
+    /// Whether the combo still triggers while typing on a focused input, which
+    /// takes every plain key press.
+    pub fn works_while_typing(&self) -> bool {
+        self.key == KeyCode::Escape
+            || self.modifiers.control()
+            || self.modifiers.alt()
+            || self.modifiers.logo()
+    }
+}
+
+impl KeyCombo {
+    /// Digit from `1` to `9` pressed without modifiers, the position of an engine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iced_native::keyboard::{KeyCode, Modifiers};
+    /// use search::keybindings::KeyCombo;
+    ///
+    /// assert_eq!(KeyCombo::new(KeyCode::Key2, Modifiers::empty()).digit(), Some(2));
+    /// assert_eq!(KeyCombo::new(KeyCode::Numpad9, Modifiers::empty()).digit(), Some(9));
+    /// assert_eq!(KeyCombo::new(KeyCode::Key0, Modifiers::empty()).digit(), None);
+    /// assert_eq!(KeyCombo::new(KeyCode::Key2, Modifiers::CTRL).digit(), None);
+    /// ```
+    pub fn digit(&self) -> Option<usize> {
+        const DIGITS: [(KeyCode, KeyCode); 9] = [
+            (KeyCode::Key1, KeyCode::Numpad1),
+            (KeyCode::Key2, KeyCode::Numpad2),
+            (KeyCode::Key3, KeyCode::Numpad3),
+            (KeyCode::Key4, KeyCode::Numpad4),
+            (KeyCode::Key5, KeyCode::Numpad5),
+            (KeyCode::Key6, KeyCode::Numpad6),
+            (KeyCode::Key7, KeyCode::Numpad7),
+            (KeyCode::Key8, KeyCode::Numpad8),
+            (KeyCode::Key9, KeyCode::Numpad9),
+        ];
+        if !self.modifiers.is_empty() {
+            return None;
+        }
+        DIGITS
+            .iter()
+            .position(|(key, numpad)| self.key == *key || self.key == *numpad)
+            .map(|position| position + 1)
+    }
+}
+
+impl FromStr for KeyCombo {
+    type Err = String;
+
+    fn from_str(combo: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<String> = combo
+            .split('+')
+            .map(|part| part.trim().to_lowercase())
+            .collect();
+        let key = parts.pop().unwrap_or_default();
+        let key = key_code(&key).ok_or_else(|| format!("unknown key `{key}` in `{combo}`"))?;
+
+        let mut modifiers = Modifiers::empty();
+        for part in parts {
+            modifiers |= match part.as_str() {
+                "ctrl" | "control" => Modifiers::CTRL,
+                "alt" | "option" => Modifiers::ALT,
+                "shift" => Modifiers::SHIFT,
+                "logo" | "super" | "win" | "meta" => Modifiers::LOGO,
+                "cmd" | "command" => Modifiers::COMMAND,
+                _ => return Err(format!("unknown modifier `{part}` in `{combo}`")),
+            };
+        }
+        Ok(Self::new(key, modifiers))
+    }
+}
+
+fn key_code(name: &str) -> Option<KeyCode> {
+    const LETTERS: [KeyCode; 26] = [
+        KeyCode::A,
+        KeyCode::B,
+        KeyCode::C,
+        KeyCode::D,
+        KeyCode::E,
+        KeyCode::F,
+        KeyCode::G,
+        KeyCode::H,
+        KeyCode::I,
+        KeyCode::J,
+        KeyCode::K,
+        KeyCode::L,
+        KeyCode::M,
+        KeyCode::N,
+        KeyCode::O,
+        KeyCode::P,
+        KeyCode::Q,
+        KeyCode::R,
+        KeyCode::S,
+        KeyCode::T,
+        KeyCode::U,
+        KeyCode::V,
+        KeyCode::W,
+        KeyCode::X,
+        KeyCode::Y,
+        KeyCode::Z,
+    ];
+    const DIGITS: [KeyCode; 10] = [
+        KeyCode::Key0,
+        KeyCode::Key1,
+        KeyCode::Key2,
+        KeyCode::Key3,
+        KeyCode::Key4,
+        KeyCode::Key5,
+        KeyCode::Key6,
+        KeyCode::Key7,
+        KeyCode::Key8,
+        KeyCode::Key9,
+    ];
+    const FUNCTIONS: [KeyCode; 12] = [
+        KeyCode::F1,
+        KeyCode::F2,
+        KeyCode::F3,
+        KeyCode::F4,
+        KeyCode::F5,
+        KeyCode::F6,
+        KeyCode::F7,
+        KeyCode::F8,
+        KeyCode::F9,
+        KeyCode::F10,
+        KeyCode::F11,
+        KeyCode::F12,
+    ];
+
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_lowercase() {
+            return Some(LETTERS[(c as u8 - b'a') as usize]);
+        }
+        if c.is_ascii_digit() {
+            return Some(DIGITS[(c as u8 - b'0') as usize]);
+        }
+    }
+    if let Some(n) = name.strip_prefix('f').and_then(|n| n.parse::<usize>().ok()) {
+        return FUNCTIONS.get(n.checked_sub(1)?).copied();
+    }
+
+    Some(match name {
+        "escape" | "esc" => KeyCode::Escape,
+        "enter" | "return" => KeyCode::Enter,
+        "space" => KeyCode::Space,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "," | "comma" => KeyCode::Comma,
+        "." | "period" => KeyCode::Period,
+        "/" | "slash" => KeyCode::Slash,
+        "\\" | "backslash" => KeyCode::Backslash,
+        ";" | "semicolon" => KeyCode::Semicolon,
+        "'" | "apostrophe" => KeyCode::Apostrophe,
+        "-" | "minus" => KeyCode::Minus,
+        "=" | "equals" => KeyCode::Equals,
+        "[" | "lbracket" => KeyCode::LBracket,
+        "]" | "rbracket" => KeyCode::RBracket,
+        "`" | "grave" => KeyCode::Grave,
+        _ => return None,
+    })
+}
+
+/// [`Keymap`] resolves the pressed combos to the action bound to them.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<KeyCombo, Action>,
+}
+
+impl Keymap {
+    /// Builds the keymap from the `keybindings` config, actions left out of it keep
+    /// their default combo and an empty combo unbinds them.
+    ///
+    /// Unknown actions, invalid combos and combos bound twice are skipped, returning
+    /// a warning for each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use iced_native::keyboard::{KeyCode, Modifiers};
+    /// use search::keybindings::{Action, KeyCombo, Keymap};
+    ///
+    /// let config = BTreeMap::from([
+    ///     ("toggle_theme".to_string(), "ctrl+e".to_string()),
+    ///     ("clear_history".to_string(), "ctrl+e".to_string()),
+    ///     ("cycle_engine".to_string(), "".to_string()),
+    ///     ("fly".to_string(), "f".to_string()),
+    /// ]);
+    /// let (keymap, warnings) = Keymap::new(&config);
+    ///
+    /// let combo = KeyCombo::new(KeyCode::E, Modifiers::CTRL);
+    /// assert_eq!(keymap.action(&combo), Some(Action::ToggleTheme));
+    /// assert_eq!(keymap.action(&KeyCombo::new(KeyCode::E, Modifiers::empty())), None);
+    /// assert_eq!(
+    ///     keymap.action(&KeyCombo::new(KeyCode::Escape, Modifiers::empty())),
+    ///     Some(Action::Back)
+    /// );
+    /// assert_eq!(warnings.len(), 2);
+    /// ```
+    pub fn new(config: &BTreeMap<String, String>) -> (Self, Vec<String>) {
+        let mut warnings: Vec<String> = config
+            .keys()
+            .filter(|name| Action::from_name(name).is_none())
+            .map(|name| format!("unknown action `{name}`"))
+            .collect();
+
+        let mut bindings: HashMap<KeyCombo, Action> = HashMap::new();
+        for action in Action::ALL {
+            let combo = match config.get(action.name()) {
+                Some(combo) => combo.as_str(),
+                None => action.default_combo().unwrap_or_default(),
+            };
+            if combo.trim().is_empty() {
+                continue;
+            }
+            let parsed = match combo.parse::<KeyCombo>() {
+                Ok(parsed) => parsed,
+                Err(err) => {
+                    warnings.push(format!("{}: {err}", action.name()));
+                    continue;
+                }
+            };
+            if let Some(bound) = bindings.get(&parsed) {
+                warnings.push(format!(
+                    "`{combo}` is bound to both {} and {}, keeping {}",
+                    bound.name(),
+                    action.name(),
+                    bound.name()
+                ));
+                continue;
+            }
+            bindings.insert(parsed, action);
+        }
+        (Self { bindings }, warnings)
+    }
+
+    pub fn action(&self, combo: &KeyCombo) -> Option<Action> {
+        self.bindings.get(combo).copied()
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&BTreeMap::new()).0
+    }
+}
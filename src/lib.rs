@@ -1,2 +1,27 @@
+pub mod app;
+pub mod appearance;
 pub mod components;
+#[cfg(feature = "deep-link")]
+pub mod deep_link;
+pub mod engines;
+pub mod error;
+pub mod export;
+#[cfg(feature = "favicons")]
+pub mod favicon;
+pub mod history;
+pub mod i18n;
+pub mod keybindings;
+pub mod limiter;
+pub mod opener;
+pub mod pipeline;
+pub mod preferences;
+pub mod query;
+#[cfg(feature = "previews")]
+pub mod results;
+#[cfg(feature = "spellcheck")]
+pub mod spellcheck;
+pub mod storage;
 pub mod styles;
+pub mod suggest;
+#[cfg(feature = "symbols")]
+pub mod symbols;
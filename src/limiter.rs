@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+
+/// [`Limiter`] caps how many network requests run at once.
+///
+/// Requests past the limit wait for a free slot. Droppable ones, like completions
+/// that are stale as soon as the next key is typed, only keep the latest one
+/// waiting, so fast typing doesn't pile them up.
+///
+/// # Examples
+///
+/// ```
+/// use search::limiter::Limiter;
+///
+/// let mut limiter = Limiter::new(1);
+///
+/// assert_eq!(limiter.start("favicon so", false), Some("favicon so"));
+/// assert_eq!(limiter.start("favicon se", false), None);
+/// assert_eq!(limiter.start("complete r", true), None);
+/// assert_eq!(limiter.start("complete ru", true), None);
+///
+/// assert_eq!(limiter.finish(), Some("favicon se"));
+/// assert_eq!(limiter.finish(), Some("complete ru"));
+/// assert_eq!(limiter.finish(), None);
+/// assert_eq!(limiter.running(), 0);
+/// ```
+#[derive(Debug)]
+pub struct Limiter<T> {
+    max: usize,
+    running: usize,
+    queue: VecDeque<(T, bool)>,
+}
+
+impl<T> Limiter<T> {
+    pub fn new(max: usize) -> Self {
+        Self {
+            max,
+            running: 0,
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Returns `request` when it can start right away, otherwise it waits for a slot.
+    ///
+    /// A waiting `droppable` request replaces the one already waiting, if any.
+    pub fn start(&mut self, request: T, droppable: bool) -> Option<T> {
+        if self.running < self.max {
+            self.running += 1;
+            return Some(request);
+        }
+        if droppable {
+            self.queue.retain(|(_, queued)| !queued);
+        }
+        self.queue.push_back((request, droppable));
+        None
+    }
+
+    /// Frees the slot of a finished request, returning the next one to start.
+    pub fn finish(&mut self) -> Option<T> {
+        self.running = self.running.saturating_sub(1);
+        let (request, _) = self.queue.pop_front()?;
+        self.running += 1;
+        Some(request)
+    }
+
+    /// Requests currently running.
+    pub fn running(&self) -> usize {
+        self.running
+    }
+}
@@ -1,294 +1,142 @@
-use iced::{
-    alignment::{Horizontal, Vertical},
-    executor,
-    widget::{
-        button, column, container, horizontal_rule, horizontal_space, row, scrollable, text,
-        text_input, Container,
-    },
-    Alignment, Application, Command, Font, Length, Settings,
-};
-use iced_native::Pixels;
+use iced::{window, Application, Settings};
+#[cfg(feature = "deep-link")]
+use search::deep_link::DeepLink;
 use search::{
-    components::tags::itag,
-    styles::modern::{
-        self,
-        modern_widget::{Element, Renderer, Row, Text},
-        ModernButton, ModernColor, ModernContainer, ModernTheme,
-    },
+    app::{App, Message},
+    engines::SearchEngine,
+    opener::{self, OpenMode},
+    query, storage,
 };
-use webbrowser;
-
-use rand::seq::SliceRandom;
-use rand::thread_rng;
 
 fn main() -> iced::Result {
+    let portable = std::env::args().any(|arg| arg == "--portable");
+    if let Some(dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| storage::portable_dir(&exe, portable))
+    {
+        storage::set_portable(dir);
+    }
+    if let Some(code) = cli_search() {
+        std::process::exit(code);
+    }
     App::run(Settings {
         default_font: Some(include_bytes!("fonts/Inter-Regular.otf")),
+        flags: launch_messages(),
+        exit_on_close_request: false,
+        window: window_settings(),
         ..Default::default()
     })
 }
 
-struct Inputs {
-    pub query: String,
-    pub enabled: bool,
-}
-
-struct App {
-    theme: ModernTheme,
-    toggler: bool,
-    inputs: Inputs,
-    searches: Vec<String>,
+/// Window settings bringing back the size and position it was closed with.
+fn window_settings() -> window::Settings {
+    let preferences = storage::read_preferences(&storage::preferences_path());
+    let defaults = window::Settings::default();
+    let position = match (preferences.window_position, preferences.window_position()) {
+        (_, Some((x, y))) => window::Position::Specific(x, y),
+        // Left somewhere out of reach, like a monitor that is gone.
+        (Some(_), None) => window::Position::Centered,
+        (None, None) => defaults.position,
+    };
+    window::Settings {
+        size: preferences.window_size.unwrap_or(defaults.size),
+        position,
+        ..defaults
+    }
 }
 
-#[allow(dead_code)]
-#[derive(Debug, Clone)]
-enum Message {
-    OnPressing,
-    TagSelected(String /* name of the tag */),
-    QueryChange(String),
-    OnChangingTheme,
-    SetSearch(String),
-    RemoveSearch(usize),
+/// Runs `--search <engine> <query>` without opening the window, returning the exit
+/// code. `None` when launched without it.
+fn cli_search() -> Option<i32> {
+    let mut args = std::env::args().skip(1).filter(|arg| arg != "--portable");
+    if args.next().as_deref() != Some("--search") {
+        return None;
+    }
+    let Some(engine) = args.next() else {
+        eprintln!("usage: capy-search --search <engine> <query>");
+        return Some(2);
+    };
+    let Some(engine) = SearchEngine::from_alias(&engine) else {
+        eprintln!("unknown engine `{engine}`");
+        return Some(2);
+    };
+    let query = args.collect::<Vec<_>>().join(" ");
+    let Some(query) = query::prepare(&query, true) else {
+        eprintln!("nothing to search");
+        return Some(2);
+    };
+    match opener::run_search(engine, &query, false, OpenMode::NewTab) {
+        Ok(()) => Some(0),
+        Err(err) => {
+            eprintln!("couldn't open the browser: {err}");
+            Some(err.exit_code())
+        }
+    }
 }
 
-const ICON_FONT: Font = Font::External {
-    name: "icons",
-    bytes: include_bytes!("fonts/bootstrap-icons.ttf"),
-};
-
-const BOLD_FONT: Font = Font::External {
-    name: "bold font",
-    bytes: include_bytes!("fonts/Inter-Bold.otf"),
-};
-
-impl Application for App {
-    type Executor = executor::Default;
-    type Message = Message;
-    type Theme = ModernTheme;
-    type Flags = ();
-
-    fn new(_flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
-        (
-            App {
-                theme: ModernTheme::Dark,
-                toggler: false,
-                inputs: Inputs {
-                    query: String::new(),
-                    enabled: true,
-                },
-                searches: Vec::new(),
-            },
-            Command::none(),
-        )
+/// Query given as plain words on launch, like `capy-search "rust lifetimes"`, and
+/// whether `--run` asked to search it right away. Deep links and `--portable` aren't
+/// part of it, even when built without the `deep-link` feature.
+fn launch_query(args: impl IntoIterator<Item = String>) -> Option<(String, bool)> {
+    let mut run = false;
+    let mut words = Vec::new();
+    for arg in args {
+        if arg == "--run" {
+            run = true;
+        } else if arg != "--portable" && !arg.starts_with("capy-search://") {
+            words.push(arg);
+        }
     }
+    let query = words.join(" ");
+    (!query.trim().is_empty()).then_some((query, run))
+}
 
-    fn title(&self) -> String {
-        "Capy search".into()
+/// Messages emitted right after startup, computed from the launch args.
+fn launch_messages() -> Vec<Message> {
+    let mut messages = Vec::new();
+    if let Some((query, run)) = launch_query(std::env::args().skip(1)) {
+        messages.push(Message::QueryChange(query));
+        if run {
+            messages.push(Message::OnPressing);
+        }
     }
-
-    fn update(&mut self, message: Self::Message) -> iced::Command<Self::Message> {
-        static PAGES: [&str; 2] = [
-            "https://stackoverflow.com/search?q=",
-            "https://stackexchange.com/search?q=",
-        ];
-        match message {
-            Message::OnPressing => {
-                if !self.inputs.query.trim().is_empty() {
-                    self.searches
-                        .insert(0, self.inputs.query.clone().trim().into());
-                    PAGES.iter().for_each(|url| {
-                        webbrowser::open(
-                            format!("{url}{query}", query = self.inputs.query.trim()).as_str(),
-                        )
-                        .unwrap();
-                    })
-                }
-            }
-            Message::OnChangingTheme => {
-                self.toggler = !self.toggler;
-                self.theme = if self.toggler {
-                    ModernTheme::Light
-                } else {
-                    ModernTheme::Dark
-                }
-            }
-            Message::QueryChange(query) | Message::SetSearch(query) => self.inputs.query = query,
-            Message::TagSelected(tag) => {
-                println!("{tag}")
-            }
-            Message::RemoveSearch(id) => {
-                println!("removing: {}", self.searches[id]);
-                self.searches.remove(id);
-            }
+    #[cfg(feature = "deep-link")]
+    if let Some(link) = std::env::args()
+        .skip(1)
+        .find_map(|arg| DeepLink::parse(&arg))
+    {
+        messages.push(Message::QueryChange(link.query));
+        if let Some(engine) = link.engine {
+            messages.push(Message::SelectEngine(engine));
+        }
+        if link.run {
+            messages.push(Message::OnPressing);
         }
-        Command::none()
     }
+    messages
+}
 
-    fn view(&self) -> Element<Message> {
-        static PLACEHOLDERS: [&str; 3] = [
-            "Search anything...",
-            "Give me your question...",
-            "Let step on that errors...",
-        ];
-        let mut rng = thread_rng();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let placeholder = *PLACEHOLDERS.choose(&mut rng).unwrap();
+    #[test]
+    fn launch_args_fill_the_query() {
+        let args = |args: &[&str]| launch_query(args.iter().map(|arg| arg.to_string()));
 
-        let title = container(
-            column![
-                Text::new("Capy")
-                    .size(75)
-                    .font(BOLD_FONT)
-                    .style(ModernColor::Custom(252.0, 187.0, 150.0)),
-                Text::new("Programmer search engine").size(18)
-            ]
-            .spacing(15)
-            .align_items(Alignment::Center),
+        assert_eq!(args(&[]), None);
+        assert_eq!(
+            args(&["rust lifetime"]),
+            Some(("rust lifetime".to_string(), false))
         );
-
-        let tags = row(vec![
-            itag(
-                "images/stack-overflow.png",
-                (252.0, 187.0, 150.0),
-                Message::TagSelected("overflow".into()),
-            )
-            .into(),
-            itag(
-                "images/stack-exchange.png",
-                (175.0, 197.0, 226.0),
-                Message::TagSelected("exchange".into()),
-            )
-            .into(),
-            button(icon('\u{F1D2}', 16))
-                .height(30)
-                .width(30)
-                .padding(6.2)
-                .on_press(Message::OnChangingTheme)
-                .style(ModernButton::Secondary)
-                .into(),
-        ])
-        .spacing(10);
-
-        let input_and_button = container(
-            row![
-                text_input(placeholder, &self.inputs.query)
-                    .on_input(Message::QueryChange)
-                    .padding([12, 20]),
-                button(icon('\u{F144}', 16))
-                    .height(30)
-                    .width(30)
-                    .padding(6.2)
-                    .style(if self.inputs.query.trim().is_empty() {
-                        ModernButton::Secondary
-                    } else {
-                        ModernButton::Principal
-                    })
-                    .on_press(Message::OnPressing),
-            ]
-            .width(595)
-            .align_items(Alignment::Center),
-        )
-        .width(610)
-        .center_x()
-        .center_y()
-        .style(ModernContainer::Input);
-
-        let principal_container: container::Container<Message, Renderer> = container(
-            column![title, input_and_button]
-                .align_items(Alignment::Center)
-                .spacing(30),
+        assert_eq!(
+            args(&["--run", "borrow", "checker"]),
+            Some(("borrow checker".to_string(), true))
+        );
+        assert_eq!(args(&["capy-search://search?q=rust"]), None);
+        assert_eq!(
+            args(&["--portable", "iced"]),
+            Some(("iced".to_string(), false))
         );
-
-        let historial_container = if self.searches.is_empty() {
-            empty_message("You didn't searched anything yet...")
-        } else {
-            show_historial(&self.searches)
-        };
-
-        let principal_box = container(container(
-            column![
-                principal_container,
-                tags,
-                horizontal_rule(1),
-                historial_container
-            ]
-            .align_items(Alignment::Center)
-            .spacing(15),
-        ))
-        .padding(10)
-        .width(Length::Fill)
-        .height(Length::Fill)
-        .center_x()
-        .center_y();
-
-        principal_box.into()
-    }
-
-    fn theme(&self) -> Self::Theme {
-        self.theme
     }
 }
-
-fn icon(unicode: char, size: impl Into<Pixels>) -> Text<'static> {
-    text(unicode.to_string())
-        .font(ICON_FONT)
-        .horizontal_alignment(Horizontal::Center)
-        .size(size)
-}
-
-fn historial_text(query: &str, id: usize) -> Element<'static, Message> {
-    Row::new()
-        .push(
-            button(
-                text(query)
-                    .size(18)
-                    .style(ModernColor::Custom(160.0, 160.0, 160.0)),
-            )
-            .style(ModernButton::Text)
-            .on_press(Message::SetSearch(query.to_string())),
-        )
-        .push(horizontal_space(10))
-        .push(
-            button(icon('\u{F62A}', 18).style(ModernColor::Custom(160.0, 160.0, 160.0)))
-                .on_press(Message::RemoveSearch(id))
-                .style(ModernButton::Text),
-        )
-        .align_items(Alignment::Center)
-        .into()
-}
-
-fn show_historial(queries: &[String]) -> modern::modern_widget::Container<'static, Message> {
-    let data: Vec<Element<Message>> = queries
-        .iter()
-        .enumerate()
-        .map(|(id, q)| historial_text(q.trim(), id))
-        .collect();
-    container(
-        scrollable(
-            column(data)
-                .padding([20, 30])
-                .align_items(Alignment::Start)
-                .spacing(5),
-        )
-        .width(580),
-    )
-    .width(610)
-    .height(200)
-    .style(ModernContainer::Historial)
-}
-
-fn empty_message(msg: &str) -> Container<'_, Message, Renderer> {
-    container(
-        text(msg)
-            .width(Length::Fill)
-            .size(20)
-            .vertical_alignment(Vertical::Center)
-            .horizontal_alignment(Horizontal::Center)
-            .style(ModernColor::Custom(82.0, 81.0, 90.0)),
-    )
-    .width(610)
-    .height(200)
-    .center_x()
-    .center_y()
-    .style(ModernContainer::Historial)
-}
@@ -0,0 +1,168 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{engines::SearchEngine, error::Result};
+
+/// Time during which opening the same url again is ignored.
+pub const DEDUPE_WINDOW: Duration = Duration::from_secs(2);
+
+/// [`OpenMode`] is the browser tab a search opens in.
+///
+/// # Examples
+///
+/// ```
+/// use search::opener::OpenMode;
+/// use search::preferences::Preferences;
+///
+/// let preferences: Preferences = toml::from_str(r#"open_mode = "reuse""#).unwrap();
+///
+/// assert_eq!(preferences.open_mode, OpenMode::Reuse);
+/// assert_eq!(Preferences::default().open_mode, OpenMode::NewTab);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenMode {
+    /// Every search gets a tab of its own.
+    #[default]
+    NewTab,
+    /// The search replaces the page of the front tab.
+    ///
+    /// Only supported on macOS with Safari or Chrome open, otherwise a new tab is
+    /// opened.
+    Reuse,
+}
+
+/// [`OpenGuard`] remembers the recently opened urls to avoid duplicated tabs.
+#[derive(Debug, Default)]
+pub struct OpenGuard {
+    recent: Vec<(String, Instant)>,
+}
+
+impl OpenGuard {
+    /// Returns `true` when `url` wasn't opened during the last [`DEDUPE_WINDOW`],
+    /// remembering it as opened at `now`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use search::opener::OpenGuard;
+    ///
+    /// let mut guard = OpenGuard::default();
+    /// let now = Instant::now();
+    ///
+    /// assert!(guard.allow("https://stackoverflow.com/search?q=rust", now));
+    /// assert!(!guard.allow("https://stackoverflow.com/search?q=rust", now + Duration::from_secs(1)));
+    /// assert!(guard.allow("https://stackexchange.com/search?q=rust", now + Duration::from_secs(1)));
+    /// assert!(guard.allow("https://stackoverflow.com/search?q=rust", now + Duration::from_secs(3)));
+    /// ```
+    pub fn allow(&mut self, url: &str, now: Instant) -> bool {
+        self.recent
+            .retain(|(_, opened)| now.saturating_duration_since(*opened) < DEDUPE_WINDOW);
+        if self.recent.iter().any(|(recent, _)| recent == url) {
+            return false;
+        }
+        self.recent.push((url.to_string(), now));
+        true
+    }
+
+    /// Forgets every url, so the next open always goes through.
+    pub fn reset(&mut self) {
+        self.recent.clear();
+    }
+}
+
+/// Searches `query` on `engine`, opening the results in the default browser.
+///
+/// Every entry point that launches a search goes through here: the `--search`
+/// command line mode directly, the window and the deep links through [`open_query`].
+///
+/// The query is percent-encoded by [`SearchEngine::url`], so spaces, `#`, `+` and
+/// any unicode reach the engine as they were typed.
+pub fn run_search(
+    engine: SearchEngine,
+    query: &str,
+    background: bool,
+    mode: OpenMode,
+) -> Result<()> {
+    open(&engine.url(query), background, mode)
+}
+
+/// Runs [`run_search`] on a blocking thread, so the caller isn't held while the
+/// browser starts.
+pub async fn open_query(
+    query: String,
+    engine: SearchEngine,
+    background: bool,
+    mode: OpenMode,
+) -> Result<()> {
+    tokio::task::spawn_blocking(move || run_search(engine, &query, background, mode))
+        .await
+        .map_err(std::io::Error::other)?
+}
+
+/// Opens `url` like [`open`], on a blocking thread so the caller isn't held.
+pub async fn open_async(url: String, background: bool, mode: OpenMode) -> Result<()> {
+    tokio::task::spawn_blocking(move || open(&url, background, mode))
+        .await
+        .map_err(std::io::Error::other)?
+}
+
+/// Opens `url` in the default browser.
+///
+/// With `background` the browser is left behind the app, so several searches can be
+/// queued without losing focus. Only macOS supports it, through `open -g`; other
+/// platforms always bring the browser to the front.
+///
+/// [`OpenMode::Reuse`] falls back to a new tab when there's no tab to reuse.
+pub fn open(url: &str, background: bool, mode: OpenMode) -> Result<()> {
+    if mode == OpenMode::Reuse && reuse_tab(url, background) {
+        return Ok(());
+    }
+    if background && cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+            .args(["-g", url])
+            .status()?;
+        return Ok(());
+    }
+    Ok(webbrowser::open(url)?)
+}
+
+/// Points the front tab of a running Safari or Chrome to `url`, `false` when
+/// neither has a window open.
+#[cfg(target_os = "macos")]
+fn reuse_tab(url: &str, background: bool) -> bool {
+    const SCRIPT: &str = r#"on run argv
+    set target to item 1 of argv
+    if application "Safari" is running then
+        tell application "Safari"
+            if (count of windows) > 0 then
+                set URL of current tab of front window to target
+                if item 2 of argv is "front" then activate
+                return "reused"
+            end if
+        end tell
+    end if
+    if application "Google Chrome" is running then
+        tell application "Google Chrome"
+            if (count of windows) > 0 then
+                set URL of active tab of front window to target
+                if item 2 of argv is "front" then activate
+                return "reused"
+            end if
+        end tell
+    end if
+end run"#;
+    let side = if background { "back" } else { "front" };
+    std::process::Command::new("osascript")
+        .args(["-e", SCRIPT, url, side])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).trim() == "reused")
+}
+
+/// Tabs can't be reused outside macOS.
+#[cfg(not(target_os = "macos"))]
+fn reuse_tab(_url: &str, _background: bool) -> bool {
+    false
+}
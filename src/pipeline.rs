@@ -0,0 +1,135 @@
+use crate::{
+    engines::SearchEngine,
+    query::{self, Bang},
+};
+
+/// Options the transforms read, taken from the preferences.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryOptions {
+    pub trim: bool,
+    pub quote: bool,
+}
+
+/// [`QueryContext`] is a query on its way to be searched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryContext {
+    pub query: String,
+    /// Engine the query goes to, `None` for every engine.
+    pub engine: Option<SearchEngine>,
+    /// Name of the custom engine the query goes to instead of `engine`.
+    pub custom: Option<String>,
+    pub opts: QueryOptions,
+}
+
+impl QueryContext {
+    pub fn new(query: impl Into<String>, engine: Option<SearchEngine>, opts: QueryOptions) -> Self {
+        Self {
+            query: query.into(),
+            engine,
+            custom: None,
+            opts,
+        }
+    }
+}
+
+/// [`QueryTransform`] is a stage of the [`Pipeline`].
+pub trait QueryTransform {
+    fn apply(&self, context: QueryContext) -> QueryContext;
+}
+
+/// Sends the query to the engine of its leading `!engine`, removing it. The bang
+/// wins over a selected custom engine.
+pub struct StripBang;
+
+impl QueryTransform for StripBang {
+    fn apply(&self, context: QueryContext) -> QueryContext {
+        match query::parse_bang(&context.query) {
+            Some(Bang::Known(engine, rest)) => QueryContext {
+                query: rest.to_string(),
+                engine: Some(engine),
+                custom: None,
+                ..context
+            },
+            _ => context,
+        }
+    }
+}
+
+/// Trims the query and collapses its inner whitespace when `trim` is on, see
+/// [`query::collapse_whitespace`].
+pub struct Trim;
+
+impl QueryTransform for Trim {
+    fn apply(&self, context: QueryContext) -> QueryContext {
+        if !context.opts.trim {
+            return context;
+        }
+        QueryContext {
+            query: query::collapse_whitespace(&context.query),
+            ..context
+        }
+    }
+}
+
+/// Wraps the query in quotes when `quote` is on, see [`query::quote`].
+pub struct Quote;
+
+impl QueryTransform for Quote {
+    fn apply(&self, context: QueryContext) -> QueryContext {
+        if !context.opts.quote || context.query.trim().is_empty() {
+            return context;
+        }
+        QueryContext {
+            query: query::quote(&context.query),
+            ..context
+        }
+    }
+}
+
+/// [`Pipeline`] runs its transforms in order over each submitted query.
+///
+/// # Examples
+///
+/// ```
+/// use search::engines::SearchEngine;
+/// use search::pipeline::{Pipeline, QueryContext, QueryOptions};
+///
+/// let opts = QueryOptions { trim: true, quote: true };
+/// let context = Pipeline::default().run(QueryContext::new(" !se  borrow checker ", None, opts));
+///
+/// assert_eq!(context.query, r#""borrow checker""#);
+/// assert_eq!(context.engine, Some(SearchEngine::StackExchange));
+///
+/// let opts = QueryOptions { trim: false, quote: false };
+/// let selected = Some(SearchEngine::StackOverflow);
+/// let context = Pipeline::default().run(QueryContext::new("lifetimes ", selected, opts));
+///
+/// assert_eq!(context, QueryContext::new("lifetimes ", selected, opts));
+///
+/// let opts = QueryOptions { trim: true, quote: false };
+/// let context = Pipeline::default().run(QueryContext::new("rust   async ", None, opts));
+///
+/// assert_eq!(context.query, "rust async");
+/// ```
+pub struct Pipeline {
+    stages: Vec<Box<dyn QueryTransform>>,
+}
+
+impl Pipeline {
+    pub fn new(stages: Vec<Box<dyn QueryTransform>>) -> Self {
+        Self { stages }
+    }
+
+    pub fn run(&self, context: QueryContext) -> QueryContext {
+        self.stages
+            .iter()
+            .fold(context, |context, stage| stage.apply(context))
+    }
+}
+
+impl Default for Pipeline {
+    /// The built-in stages: [`StripBang`], [`Trim`] and [`Quote`].
+    fn default() -> Self {
+        Self::new(vec![Box::new(StripBang), Box::new(Trim), Box::new(Quote)])
+    }
+}
@@ -0,0 +1,379 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use serde::{de::IntoDeserializer, Deserialize, Deserializer, Serialize};
+
+use crate::{
+    engines::{CustomEngine, SearchEngine},
+    history::{HistoryGroup, MAX_HISTORY},
+    i18n::Lang,
+    keybindings,
+    opener::OpenMode,
+    styles::modern::{ModernTheme, RGBColor},
+};
+
+/// [`Preferences`] are the user settings that tweak how the app behaves.
+///
+/// Missing keys on the config file take their default value.
+///
+/// # Examples
+///
+/// ```
+/// use search::preferences::Preferences;
+/// use search::styles::modern::ModernTheme;
+///
+/// let preferences: Preferences = toml::from_str(r#"theme = "high_contrast""#).unwrap();
+/// assert_eq!(preferences.theme, Some(ModernTheme::HighContrast));
+///
+/// // Themes this version doesn't know fall back to the dark one.
+/// let preferences: Preferences = toml::from_str(r#"theme = "sepia""#).unwrap();
+/// assert_eq!(preferences.theme, Some(ModernTheme::Dark));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    /// Trims the query and collapses its inner whitespace before storing and searching it.
+    pub trim_queries: bool,
+    /// Strips the terminal colors and control characters of typed and pasted text.
+    pub sanitize_input: bool,
+    /// Wraps the query in quotes when searching, to match the exact phrase.
+    pub quote_queries: bool,
+    /// Clears the input whenever the selected engine changes.
+    pub clear_on_engine_switch: bool,
+    /// Empties the input once its searches are added to the history.
+    pub clear_after_search: bool,
+    /// Skips opening an url that was just opened, avoiding double submits.
+    pub dedupe_opens: bool,
+    /// Opens queries that are links as they are, instead of searching them.
+    pub open_urls_directly: bool,
+    /// Offers to bring back the query left in the input when the app was closed.
+    pub restore_last_query: bool,
+    /// Suggests a broader engine for queries longer than `long_query_words` words.
+    pub long_query_hint: bool,
+    pub long_query_words: usize,
+    /// Tabs a single submit opens before asking for confirmation.
+    pub confirm_tab_threshold: usize,
+    /// Opens the searches without bringing the browser to the front.
+    ///
+    /// Only supported on macOS.
+    pub background_open: bool,
+    /// Whether searches open on a new tab or replace the front one.
+    pub open_mode: OpenMode,
+    /// Hits listed on the results card, the rest of the fetched ones are left out.
+    ///
+    /// Only used when built with the `previews` feature.
+    pub result_limit: usize,
+    /// Copies the query to the clipboard on every keystroke, for tools watching it.
+    pub live_clipboard: bool,
+    /// Hides the history row actions until they are hovered.
+    pub compact_rows: bool,
+    /// Closes the suggestions dropdown when clicking anywhere else.
+    pub close_suggestions_on_click_outside: bool,
+    /// Beeps when an empty query is submitted.
+    ///
+    /// Only used when built with the `sound` feature.
+    pub sound_feedback: bool,
+    /// Searches kept in the history, the oldest go first once it's full.
+    pub history_limit: usize,
+    /// How the history list is organized.
+    pub history_group: HistoryGroup,
+    /// Milliseconds to wait before writing history changes to disk, so bursts of
+    /// changes are saved once. `0` saves every change right away.
+    pub history_save_delay_ms: u64,
+    /// Height of the engine tags, in pixels.
+    pub tag_icon_size: u16,
+    /// Color of the principal buttons and the selected tag outline, as `(r, g, b)`.
+    ///
+    /// `None` keeps the one of the theme.
+    pub accent: Option<RGBColor>,
+    /// Theme picked last, `None` follows the system one.
+    #[serde(deserialize_with = "lenient_theme")]
+    pub theme: Option<ModernTheme>,
+    /// Id of the engine selected on launch, `None` searches every engine.
+    pub default_engine: Option<String>,
+    /// Language of the interface, `None` follows the system locale.
+    pub language: Option<Lang>,
+    /// Multiplies the text sizes of the main screen, to read it better.
+    pub font_scale: f32,
+    /// Spacing of the main screen, `compact` fits it in smaller windows.
+    pub density: Density,
+    /// Ids of the engines in the order their tags are shown.
+    pub engine_order: Vec<String>,
+    /// Ids of the engines pinned to the favorites bar.
+    pub favorite_engines: Vec<String>,
+    /// Size of the window when it was last closed, as `(width, height)`.
+    pub window_size: Option<(u32, u32)>,
+    /// Position of the window when it was last closed, as `(x, y)`.
+    pub window_position: Option<(i32, i32)>,
+    /// Project directory whose symbols are offered as completions.
+    ///
+    /// Only used when built with the `symbols` feature.
+    pub symbols_root: Option<PathBuf>,
+    /// Key combo of each shortcut action, by action name.
+    pub keybindings: BTreeMap<String, String>,
+    /// Searches opened on each engine so far, by engine id.
+    pub engine_searches: BTreeMap<String, u64>,
+    /// Sites added as `[[custom_engines]]` tables, shown after the engine tags.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub custom_engines: Vec<CustomEngine>,
+}
+
+/// [`Density`] is how much room the main screen leaves between its widgets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Density {
+    #[default]
+    Comfortable,
+    Compact,
+}
+
+impl Density {
+    /// Multiplies the spacing, paddings and history height of the main screen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::preferences::{Density, Preferences};
+    ///
+    /// let preferences: Preferences = toml::from_str("density = \"compact\"").unwrap();
+    ///
+    /// assert_eq!(preferences.density, Density::Compact);
+    /// assert!(Density::Compact.factor() < Density::Comfortable.factor());
+    /// ```
+    pub fn factor(&self) -> f32 {
+        match self {
+            Density::Comfortable => 1.0,
+            Density::Compact => 0.6,
+        }
+    }
+}
+
+/// Reads the `theme` key, unknown themes become [`ModernTheme::Dark`].
+fn lenient_theme<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<ModernTheme>, D::Error> {
+    let name = Option::<String>::deserialize(deserializer)?;
+    Ok(name.map(|name| {
+        let theme: Result<_, serde::de::value::Error> =
+            ModernTheme::deserialize(name.as_str().into_deserializer());
+        theme.unwrap_or(ModernTheme::Dark)
+    }))
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            trim_queries: true,
+            sanitize_input: true,
+            quote_queries: false,
+            clear_on_engine_switch: false,
+            clear_after_search: false,
+            dedupe_opens: true,
+            open_urls_directly: false,
+            restore_last_query: true,
+            long_query_hint: true,
+            long_query_words: 12,
+            confirm_tab_threshold: 5,
+            background_open: false,
+            open_mode: OpenMode::NewTab,
+            result_limit: 5,
+            live_clipboard: false,
+            compact_rows: false,
+            close_suggestions_on_click_outside: true,
+            sound_feedback: false,
+            history_limit: MAX_HISTORY,
+            history_group: HistoryGroup::None,
+            history_save_delay_ms: 1000,
+            tag_icon_size: 30,
+            accent: None,
+            theme: None,
+            default_engine: None,
+            language: None,
+            font_scale: 1.0,
+            density: Density::Comfortable,
+            engine_order: SearchEngine::KNOWN
+                .iter()
+                .map(|engine| engine.id().to_string())
+                .collect(),
+            favorite_engines: Vec::new(),
+            window_size: None,
+            window_position: None,
+            symbols_root: None,
+            keybindings: keybindings::default_keybindings(),
+            engine_searches: BTreeMap::new(),
+            custom_engines: Vec::new(),
+        }
+    }
+}
+
+impl Preferences {
+    /// Returns every engine following `engine_order`.
+    ///
+    /// Unknown and repeated ids are skipped and engines missing from the order go at the end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::engines::SearchEngine;
+    /// use search::preferences::Preferences;
+    ///
+    /// let preferences = Preferences {
+    ///     engine_order: vec!["se".into(), "gone".into(), "so".into(), "se".into()],
+    ///     ..Preferences::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     preferences.engines(),
+    ///     vec![
+    ///         SearchEngine::StackExchange,
+    ///         SearchEngine::StackOverflow,
+    ///         SearchEngine::DuckDuckGo
+    ///     ]
+    /// );
+    /// ```
+    pub fn engines(&self) -> Vec<SearchEngine> {
+        let mut engines = Vec::new();
+        let ordered = self
+            .engine_order
+            .iter()
+            .filter_map(|id| SearchEngine::from_id(id));
+        for engine in ordered.chain(SearchEngine::KNOWN) {
+            if !engines.contains(&engine) {
+                engines.push(engine);
+            }
+        }
+        engines
+    }
+
+    /// Returns the engine after `current` following `engine_order`, wrapping around at
+    /// the end. Without a current engine it starts from the first one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::engines::SearchEngine;
+    /// use search::preferences::Preferences;
+    ///
+    /// let preferences = Preferences::default();
+    ///
+    /// assert_eq!(preferences.next_engine(None), SearchEngine::StackOverflow);
+    /// assert_eq!(
+    ///     preferences.next_engine(Some(SearchEngine::StackOverflow)),
+    ///     SearchEngine::StackExchange
+    /// );
+    /// assert_eq!(
+    ///     preferences.next_engine(Some(SearchEngine::StackExchange)),
+    ///     SearchEngine::DuckDuckGo
+    /// );
+    /// assert_eq!(
+    ///     preferences.next_engine(Some(SearchEngine::DuckDuckGo)),
+    ///     SearchEngine::StackOverflow
+    /// );
+    /// ```
+    pub fn next_engine(&self, current: Option<SearchEngine>) -> SearchEngine {
+        let engines = self.engines();
+        let next = current
+            .and_then(|current| engines.iter().position(|engine| *engine == current))
+            .map_or(0, |position| (position + 1) % engines.len());
+        engines[next]
+    }
+
+    /// Moves `engine` one place towards the start, or the end when `forward`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::engines::SearchEngine;
+    /// use search::preferences::Preferences;
+    ///
+    /// let mut preferences = Preferences::default();
+    /// preferences.move_engine(SearchEngine::StackOverflow, true);
+    ///
+    /// assert_eq!(preferences.engine_order, vec!["se", "so", "ddg"]);
+    /// ```
+    pub fn move_engine(&mut self, engine: SearchEngine, forward: bool) {
+        let mut engines = self.engines();
+        if let Some(from) = engines.iter().position(|e| *e == engine) {
+            let to = if forward {
+                (from + 1).min(engines.len() - 1)
+            } else {
+                from.saturating_sub(1)
+            };
+            engines.swap(from, to);
+        }
+        self.engine_order = engines
+            .iter()
+            .map(|engine| engine.id().to_string())
+            .collect();
+    }
+
+    /// Returns the stored window position when the window would still be reachable
+    /// there, `None` to let it be centered instead.
+    ///
+    /// The monitors aren't known before the window opens, so a position is dropped
+    /// when it's beyond any usual desktop, like one left on a disconnected monitor
+    /// far to the side, or when the title bar would be above the top of the screen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::preferences::Preferences;
+    ///
+    /// let mut preferences = Preferences {
+    ///     window_size: Some((800, 600)),
+    ///     window_position: Some((-300, 40)),
+    ///     ..Preferences::default()
+    /// };
+    /// assert_eq!(preferences.window_position(), Some((-300, 40)));
+    ///
+    /// preferences.window_position = Some((-790, 40));
+    /// assert_eq!(preferences.window_position(), None);
+    ///
+    /// preferences.window_position = Some((20_000, 40));
+    /// assert_eq!(preferences.window_position(), None);
+    /// ```
+    pub fn window_position(&self) -> Option<(i32, i32)> {
+        /// Pixels of the window that must stay on the desktop to grab it.
+        const VISIBLE: i32 = 100;
+        /// Farthest coordinate a desktop of a few large monitors reaches.
+        const DESKTOP_EDGE: i32 = 16_384;
+
+        let (x, y) = self.window_position?;
+        let width = self.window_size.map_or(VISIBLE, |(width, _)| width as i32);
+        let reachable = x > VISIBLE - width
+            && x < DESKTOP_EDGE - VISIBLE
+            && (0..DESKTOP_EDGE - VISIBLE).contains(&y);
+        reachable.then_some((x, y))
+    }
+
+    /// Returns the favorite engines, in the order they were added.
+    pub fn favorites(&self) -> Vec<SearchEngine> {
+        self.favorite_engines
+            .iter()
+            .filter_map(|id| SearchEngine::from_id(id))
+            .collect()
+    }
+
+    /// Adds `engine` to the favorites, or removes it when it already was one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::engines::SearchEngine;
+    /// use search::preferences::Preferences;
+    ///
+    /// let mut preferences = Preferences::default();
+    /// preferences.toggle_favorite(SearchEngine::StackExchange);
+    /// assert_eq!(preferences.favorites(), vec![SearchEngine::StackExchange]);
+    ///
+    /// preferences.toggle_favorite(SearchEngine::StackExchange);
+    /// assert!(preferences.favorites().is_empty());
+    /// ```
+    pub fn toggle_favorite(&mut self, engine: SearchEngine) {
+        let id = engine.id();
+        if self.favorite_engines.iter().any(|favorite| favorite == id) {
+            self.favorite_engines.retain(|favorite| favorite != id);
+        } else {
+            self.favorite_engines.push(id.to_string());
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use crate::engines::SearchEngine;
+
+/// Prepares the raw input to be submitted, returning `None` when there's nothing to search.
+///
+/// The empty check always ignores surrounding whitespace, but the whitespace is only
+/// cleaned up, see [`collapse_whitespace`], when `trim` is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use search::query::prepare;
+///
+/// assert_eq!(prepare("  rust traits ", true), Some("rust traits".to_string()));
+/// assert_eq!(prepare("  rust traits ", false), Some("  rust traits ".to_string()));
+/// assert_eq!(prepare("rust   async \t trait", true), Some("rust async trait".to_string()));
+/// assert_eq!(prepare("   ", true), None);
+/// assert_eq!(prepare("   ", false), None);
+/// ```
+pub fn prepare(raw: &str, trim: bool) -> Option<String> {
+    if raw.trim().is_empty() {
+        return None;
+    }
+    Some(if trim {
+        collapse_whitespace(raw)
+    } else {
+        raw.to_string()
+    })
+}
+
+/// Trims `query` and turns each run of whitespace inside it into a single space.
+///
+/// # Examples
+///
+/// ```
+/// use search::query::collapse_whitespace;
+///
+/// assert_eq!(collapse_whitespace("  rust   async\n\ttrait "), "rust async trait");
+/// assert_eq!(collapse_whitespace("rust"), "rust");
+/// ```
+pub fn collapse_whitespace(query: &str) -> String {
+    query.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Fewest characters of a query worth searching, see [`is_too_short`].
+pub const MIN_QUERY_CHARS: usize = 2;
+
+/// Whether a non blank query is unlikely to find anything, being shorter than
+/// [`MIN_QUERY_CHARS`] or only punctuation.
+///
+/// # Examples
+///
+/// ```
+/// use search::query::is_too_short;
+///
+/// assert!(is_too_short(" r "));
+/// assert!(is_too_short("?!"));
+/// assert!(!is_too_short("c#"));
+/// assert!(!is_too_short("rust traits"));
+/// assert!(!is_too_short("   "));
+/// ```
+pub fn is_too_short(query: &str) -> bool {
+    let query = query.trim();
+    !query.is_empty()
+        && (query.chars().count() < MIN_QUERY_CHARS || !query.chars().any(char::is_alphanumeric))
+}
+
+/// Separator between the queries of a single submission.
+pub const QUERY_SEPARATOR: &str = ";;";
+
+/// Splits the input into each query it holds, separated by [`QUERY_SEPARATOR`] or
+/// new lines.
+///
+/// Blank queries are dropped, the rest are kept as typed so [`prepare`] can trim them.
+///
+/// # Examples
+///
+/// ```
+/// use search::query::split_queries;
+///
+/// assert_eq!(split_queries("rust traits;; iced"), vec!["rust traits", " iced"]);
+/// assert_eq!(split_queries("lifetimes\nborrow;; ;;"), vec!["lifetimes", "borrow"]);
+/// assert_eq!(split_queries("single query"), vec!["single query"]);
+/// assert!(split_queries(" ;; ").is_empty());
+/// ```
+pub fn split_queries(input: &str) -> Vec<String> {
+    input
+        .split(QUERY_SEPARATOR)
+        .flat_map(str::lines)
+        .filter(|query| !query.trim().is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether the query is a link to open rather than something to search.
+///
+/// Only `http://` and `https://` links with a host and no spaces count.
+///
+/// # Examples
+///
+/// ```
+/// use search::query::is_url;
+///
+/// assert!(is_url("https://doc.rust-lang.org/book/"));
+/// assert!(is_url(" HTTP://localhost:8080 "));
+/// assert!(!is_url("https://"));
+/// assert!(!is_url("https://docs.rs is down"));
+/// assert!(!is_url("ftp://example.com"));
+/// assert!(!is_url("how to parse https://example.com"));
+/// ```
+pub fn is_url(query: &str) -> bool {
+    let query = query.trim();
+    let Some((scheme, rest)) = query.split_once("://") else {
+        return false;
+    };
+    (scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https"))
+        && !rest.starts_with(['/', '?', '#'])
+        && !rest.is_empty()
+        && !query.contains(char::is_whitespace)
+}
+
+/// Wraps the query in double quotes so engines match the exact phrase.
+///
+/// Quotes and backslashes already in the query are escaped, so they can't close
+/// the phrase early.
+///
+/// # Examples
+///
+/// ```
+/// use search::engines::SearchEngine;
+/// use search::query::quote;
+///
+/// assert_eq!(quote("borrow checker"), r#""borrow checker""#);
+/// assert_eq!(quote(r#"say "hi""#), r#""say \"hi\"""#);
+/// assert_eq!(quote(r"C:\path"), r#""C:\\path""#);
+///
+/// // Once encoded the quotes can't break the url.
+/// assert_eq!(
+///     SearchEngine::StackOverflow.url(&quote(r#"a "b""#)),
+///     "https://stackoverflow.com/search?q=%22a%20%5C%22b%5C%22%22"
+/// );
+/// ```
+pub fn quote(query: &str) -> String {
+    let mut quoted = String::with_capacity(query.len() + 2);
+    quoted.push('"');
+    for c in query.chars() {
+        if matches!(c, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Appends a search `operator` to the query, unless it's already one of its words.
+///
+/// # Examples
+///
+/// ```
+/// use search::query::with_operator;
+///
+/// assert_eq!(with_operator("borrow checker", "is:question"), "borrow checker is:question");
+/// assert_eq!(with_operator("borrow checker ", "is:answer"), "borrow checker is:answer");
+/// assert_eq!(with_operator("", "is:answer"), "is:answer");
+/// assert_eq!(with_operator("is:answer borrow", "is:answer"), "is:answer borrow");
+/// ```
+pub fn with_operator(query: &str, operator: &str) -> String {
+    if query.split_whitespace().any(|word| word == operator) {
+        return query.to_string();
+    }
+    let query = query.trim_end();
+    if query.is_empty() {
+        operator.to_string()
+    } else {
+        format!("{query} {operator}")
+    }
+}
+
+/// [`Bang`] is the `!engine` prefix used to route a query to an engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bang<'a> {
+    /// The bang names a known engine, the rest of the query is kept.
+    Known(SearchEngine, &'a str),
+    /// The bang doesn't match any engine.
+    Unknown(&'a str, &'a str),
+}
+
+/// Splits the leading bang from the query, if any.
+///
+/// # Examples
+///
+/// ```
+/// use search::engines::SearchEngine;
+/// use search::query::{parse_bang, Bang};
+///
+/// assert_eq!(parse_bang("!so rust traits"), Some(Bang::Known(SearchEngine::StackOverflow, "rust traits")));
+/// assert_eq!(parse_bang("!stakoverflow rust"), Some(Bang::Unknown("stakoverflow", "rust")));
+/// assert_eq!(parse_bang("rust !so"), None);
+/// assert_eq!(parse_bang("! rust"), None);
+/// ```
+pub fn parse_bang(input: &str) -> Option<Bang<'_>> {
+    let input = input.trim_start();
+    let rest = input.strip_prefix('!')?;
+    let (name, query) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    if name.is_empty() {
+        return None;
+    }
+    let query = query.trim_start();
+    Some(match SearchEngine::from_alias(name) {
+        Some(engine) => Bang::Known(engine, query),
+        None => Bang::Unknown(name, query),
+    })
+}
+
+/// Removes the ANSI escape sequences and control characters of text pasted from a
+/// terminal. Tabs and line breaks become spaces, any other character is kept.
+///
+/// # Examples
+///
+/// ```
+/// use search::query::strip_control;
+///
+/// assert_eq!(strip_control("\x1b[1;31merror\x1b[0m: borrow"), "error: borrow");
+/// assert_eq!(strip_control("\x1b]0;title\x07cargo build"), "cargo build");
+/// assert_eq!(strip_control("line\none\ttab\r"), "line one tab ");
+/// assert_eq!(strip_control("nul\0 bell\x07 del\x7f"), "nul bell del");
+/// assert_eq!(strip_control("¿qué? 日本語 🦀"), "¿qué? 日本語 🦀");
+/// ```
+pub fn strip_control(s: &str) -> String {
+    let mut stripped = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                // CSI, up to its final byte.
+                Some('[') => {
+                    for c in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC, up to the bell or the string terminator.
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            '\t' | '\n' | '\r' => stripped.push(' '),
+            c if c.is_control() => {}
+            c => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+/// Accented latin letters, each folded into the letter at the same place of [`PLAIN`].
+const ACCENTED: &str =
+    "àáâãäåçèéêëìíîïñòóôõöøùúûüýÿāăąćĉċčďđēĕėęěĝğġģĥħĩīĭįıĵķĺļľŀłńņňōŏőŕŗřśŝşšţťŧũūŭůűųŵŷźżž";
+const PLAIN: &str =
+    "aaaaaaceeeeiiiinoooooouuuuyyaaaccccddeeeeegggghhiiiiijklllllnnnooorrrsssstttuuuuuuwyzzz";
+
+/// Lowercases `query` and drops its accents, so queries that only differ on them
+/// compare equal.
+///
+/// Combining marks are removed and precomposed latin letters become the plain one.
+///
+/// # Examples
+///
+/// ```
+/// use search::query::normalize;
+///
+/// assert_eq!(normalize("Café"), "cafe");
+/// assert_eq!(normalize("cafe\u{301}"), "cafe");
+/// assert_eq!(normalize("RUST Ñandú"), "rust nandu");
+/// assert_eq!(normalize("日本語"), "日本語");
+/// ```
+pub fn normalize(query: &str) -> String {
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !('\u{300}'..='\u{36f}').contains(c))
+        .map(|c| {
+            ACCENTED
+                .chars()
+                .zip(PLAIN.chars())
+                .find(|(accented, _)| *accented == c)
+                .map_or(c, |(_, plain)| plain)
+        })
+        .collect()
+}
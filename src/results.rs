@@ -0,0 +1,115 @@
+use serde::Deserialize;
+
+use crate::{
+    engines::{encode, SearchEngine},
+    error::{Result, SearchError},
+};
+
+/// Results fetched for a query, at most. The card lists the first `result_limit` of
+/// them.
+pub const MAX_RESULTS: usize = 20;
+
+/// [`ResultItem`] is a hit of a search, previewed under the input.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ResultItem {
+    pub title: String,
+    #[serde(rename = "link")]
+    pub url: String,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    items: Vec<ResultItem>,
+}
+
+/// Stack Exchange API url searching `query` on the site of `engine`.
+///
+/// The API can't search the whole network, so Stack Exchange searches preview
+/// their Stack Overflow hits.
+///
+/// # Examples
+///
+/// ```
+/// use search::engines::SearchEngine;
+/// use search::results::api_url;
+///
+/// assert_eq!(
+///     api_url("rust traits", Some(SearchEngine::StackOverflow)),
+///     "https://api.stackexchange.com/2.3/search/advanced?order=desc&sort=relevance\
+///      &pagesize=20&site=stackoverflow&q=rust%20traits"
+/// );
+/// ```
+pub fn api_url(query: &str, engine: Option<SearchEngine>) -> String {
+    let site = match engine {
+        Some(
+            SearchEngine::StackOverflow | SearchEngine::StackExchange | SearchEngine::DuckDuckGo,
+        )
+        | None => "stackoverflow",
+    };
+    format!(
+        "https://api.stackexchange.com/2.3/search/advanced?order=desc&sort=relevance\
+         &pagesize={MAX_RESULTS}&site={site}&q={}",
+        encode(query)
+    )
+}
+
+/// Reads the hits out of an API response, unescaping their titles.
+///
+/// # Examples
+///
+/// ```
+/// use search::results::{parse, ResultItem};
+///
+/// let json = r#"{"items": [{"title": "Why &quot;move&quot; closures?", "link": "https://stackoverflow.com/q/1"}]}"#;
+///
+/// assert_eq!(
+///     parse(json).unwrap(),
+///     vec![ResultItem {
+///         title: "Why \"move\" closures?".into(),
+///         url: "https://stackoverflow.com/q/1".into(),
+///     }]
+/// );
+/// assert!(parse("<html>").is_err());
+/// ```
+pub fn parse(json: &str) -> Result<Vec<ResultItem>> {
+    let response: Response =
+        serde_json::from_str(json).map_err(|err| SearchError::parse("results", err))?;
+    Ok(response
+        .items
+        .into_iter()
+        .map(|item| ResultItem {
+            title: unescape(&item.title),
+            ..item
+        })
+        .collect())
+}
+
+/// Replaces the HTML entities the API escapes titles with.
+///
+/// # Examples
+///
+/// ```
+/// use search::results::unescape;
+///
+/// assert_eq!(unescape("&lt;T&gt; &amp; &#39;a"), "<T> & 'a");
+/// ```
+pub fn unescape(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// Fetches the top hits of `query` on `engine`.
+pub async fn fetch_results(query: String, engine: Option<SearchEngine>) -> Result<Vec<ResultItem>> {
+    let network = |err: reqwest::Error| SearchError::Network(err.to_string());
+    let json = reqwest::get(api_url(&query, engine))
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(network)?
+        .text()
+        .await
+        .map_err(network)?;
+    parse(&json)
+}
@@ -0,0 +1,103 @@
+use std::{collections::HashSet, sync::OnceLock};
+
+use crate::suggest::levenshtein;
+
+/// Words the checker knows, one per line, the most common ones first.
+const DICTIONARY: &str = include_str!("dictionary.txt");
+
+/// Shortest word checked, shorter ones are too easy to mistake for another word.
+const MIN_LENGTH: usize = 4;
+
+fn dictionary() -> &'static HashSet<&'static str> {
+    static WORDS: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| DICTIONARY.lines().collect())
+}
+
+/// Finds the word `word` was most likely meant to be.
+///
+/// Returns `None` for known words, words with anything but letters, which are
+/// likely names or code, and when no single word is clearly the closest one.
+///
+/// # Examples
+///
+/// ```
+/// use search::spellcheck::correct_word;
+///
+/// assert_eq!(correct_word("compiller"), Some("compiler"));
+/// assert_eq!(correct_word("Fucntion"), Some("function"));
+/// assert_eq!(correct_word("compiler"), None);
+/// assert_eq!(correct_word("tokio"), None);
+/// assert_eq!(correct_word("u8"), None);
+/// ```
+pub fn correct_word(word: &str) -> Option<&'static str> {
+    let word = word.to_lowercase();
+    if word.len() < MIN_LENGTH
+        || !word.chars().all(|c| c.is_ascii_alphabetic())
+        || dictionary().contains(word.as_str())
+    {
+        return None;
+    }
+    // Short words only take a single typo, they'd match too much otherwise.
+    let max_distance = if word.len() <= 5 { 1 } else { 2 };
+
+    let mut best: Option<(usize, &'static str)> = None;
+    let mut tied = false;
+    for candidate in DICTIONARY.lines() {
+        let distance = levenshtein(&word, candidate);
+        if distance > max_distance {
+            continue;
+        }
+        match best {
+            Some((closest, _)) if distance > closest => {}
+            Some((closest, _)) if distance == closest => tied = true,
+            _ => {
+                best = Some((distance, candidate));
+                tied = false;
+            }
+        }
+    }
+    best.filter(|_| !tied).map(|(_, candidate)| candidate)
+}
+
+/// Corrects each misspelled word of `query`, see [`correct_word`].
+///
+/// Returns `None` when there's nothing to correct.
+///
+/// # Examples
+///
+/// ```
+/// use search::spellcheck::correct;
+///
+/// assert_eq!(
+///     correct("how to instal the rust compiller"),
+///     Some("how to install the rust compiler".to_string())
+/// );
+/// assert_eq!(correct("Borow checker"), Some("Borrow checker".to_string()));
+/// assert_eq!(correct("!so tokio runtime"), None);
+/// ```
+pub fn correct(query: &str) -> Option<String> {
+    let mut changed = false;
+    let words: Vec<String> = query
+        .split(' ')
+        .map(|word| match correct_word(word) {
+            Some(correction) => {
+                changed = true;
+                match_case(word, correction)
+            }
+            None => word.to_string(),
+        })
+        .collect();
+    changed.then(|| words.join(" "))
+}
+
+/// Capitalizes `correction` when `word` was.
+fn match_case(word: &str, correction: &str) -> String {
+    if !word.starts_with(|c: char| c.is_uppercase()) {
+        return correction.to_string();
+    }
+    let mut chars = correction.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,191 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Result, SearchError},
+    history::HistoryEntry,
+    preferences::Preferences,
+};
+
+/// Folder beside the binary set with [`set_portable`], set once on launch.
+static PORTABLE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Folder where every file of the app is stored, the one set with [`set_portable`]
+/// or else the `capy-search` folder of the OS config directory.
+pub fn config_dir() -> PathBuf {
+    if let Some(dir) = PORTABLE_DIR.get() {
+        return dir.clone();
+    }
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("capy-search")
+}
+
+/// Stores every file of the app in `dir` from now on. Only the first call counts.
+pub fn set_portable(dir: PathBuf) {
+    let _ = PORTABLE_DIR.set(dir);
+}
+
+/// Folder of the binary at `exe` when the app runs portable, because `flag` asked for
+/// it or the folder already holds a `config.toml`.
+///
+/// A portable `config.toml` wins over the one of the config directory, so the copy
+/// carried along keeps being used once it exists.
+///
+/// # Examples
+///
+/// ```
+/// use search::storage::portable_dir;
+///
+/// let dir = std::env::temp_dir().join("capy-search-portable-doc");
+/// std::fs::create_dir_all(&dir).unwrap();
+/// let exe = dir.join("capy-search");
+///
+/// assert_eq!(portable_dir(&exe, true), Some(dir.clone()));
+/// assert_eq!(portable_dir(&exe, false), None);
+///
+/// std::fs::write(dir.join("config.toml"), "").unwrap();
+/// assert_eq!(portable_dir(&exe, false), Some(dir.clone()));
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub fn portable_dir(exe: &Path, flag: bool) -> Option<PathBuf> {
+    let dir = exe.parent()?;
+    (flag || dir.join("config.toml").is_file()).then(|| dir.to_path_buf())
+}
+
+pub fn history_path() -> PathBuf {
+    config_dir().join("history.toml")
+}
+
+pub fn preferences_path() -> PathBuf {
+    config_dir().join("config.toml")
+}
+
+pub fn last_query_path() -> PathBuf {
+    config_dir().join("last_query.txt")
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct HistoryFile {
+    #[serde(default)]
+    entries: Vec<HistoryEntry>,
+}
+
+/// Serializes the history as a TOML document.
+///
+/// # Examples
+///
+/// ```
+/// use search::engines::SearchEngine;
+/// use search::history::HistoryEntry;
+/// use search::storage::{decode_history, encode_history};
+///
+/// let entries = vec![
+///     HistoryEntry::new("rust lifetimes", Some(SearchEngine::StackOverflow)),
+///     HistoryEntry::new("iced widgets", None),
+/// ];
+///
+/// let encoded = encode_history(&entries).unwrap();
+/// assert_eq!(decode_history(&encoded).unwrap(), entries);
+/// assert!(decode_history("").unwrap().is_empty());
+/// ```
+pub fn encode_history(entries: &[HistoryEntry]) -> Result<String> {
+    toml::to_string(&HistoryFile {
+        entries: entries.to_vec(),
+    })
+    .map_err(|err| SearchError::parse("history", err))
+}
+
+pub fn decode_history(contents: &str) -> Result<Vec<HistoryEntry>> {
+    toml::from_str::<HistoryFile>(contents)
+        .map(|file| file.entries)
+        .map_err(|err| SearchError::parse("history", err))
+}
+
+/// Reads the history stored at `path`, a missing file is an empty history.
+pub async fn load_history(path: PathBuf) -> Result<Vec<HistoryEntry>> {
+    match tokio::fs::read_to_string(&path).await {
+        Ok(contents) => decode_history(&contents),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Writes the history to `path`.
+///
+/// The file is written next to the destination and then renamed, so an
+/// interrupted save never leaves a truncated history behind.
+pub async fn save_history(path: PathBuf, entries: Vec<HistoryEntry>) -> Result<()> {
+    let contents = encode_history(&entries)?;
+    Ok(write_atomically(&path, contents).await?)
+}
+
+/// Reads the preferences stored at `path`, a missing file gives the defaults.
+///
+/// # Examples
+///
+/// ```
+/// use search::preferences::Preferences;
+///
+/// let preferences: Preferences = toml::from_str("compact_rows = true").unwrap();
+///
+/// assert!(preferences.compact_rows);
+/// assert!(preferences.trim_queries);
+/// ```
+pub async fn load_preferences(path: PathBuf) -> Result<Preferences> {
+    match tokio::fs::read_to_string(&path).await {
+        Ok(contents) => toml::from_str(&contents).map_err(|err| SearchError::parse("config", err)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Preferences::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Reads the preferences right away, for the settings needed before the app starts.
+///
+/// A missing or invalid file gives the defaults.
+pub fn read_preferences(path: &Path) -> Preferences {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Writes the preferences to `path`.
+pub async fn save_preferences(path: PathBuf, preferences: Preferences) -> Result<()> {
+    let contents =
+        toml::to_string_pretty(&preferences).map_err(|err| SearchError::parse("config", err))?;
+    Ok(write_atomically(&path, contents).await?)
+}
+
+/// Reads the query left in the input when the app was last closed, if any.
+pub async fn load_last_query(path: PathBuf) -> Option<String> {
+    tokio::fs::read_to_string(&path)
+        .await
+        .ok()
+        .filter(|query| !query.trim().is_empty())
+}
+
+/// Stores the query left in the input, `None` removes the stored one.
+pub async fn save_last_query(path: PathBuf, query: Option<String>) -> Result<()> {
+    let result = match query {
+        Some(query) => write_atomically(&path, query).await,
+        None => match tokio::fs::remove_file(&path).await {
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        },
+    };
+    Ok(result?)
+}
+
+async fn write_atomically(path: &Path, contents: String) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let temporary = path.with_extension("tmp");
+    tokio::fs::write(&temporary, contents).await?;
+    tokio::fs::rename(&temporary, path).await
+}
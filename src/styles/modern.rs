@@ -1,9 +1,18 @@
 use iced::{
     application, color,
-    widget::{button, container, rule, scrollable, text, text_input, toggler},
+    widget::{button, container, radio, rule, scrollable, text, text_input, toggler},
     Background, Color,
 };
 use iced_native::Vector;
+use serde::{Deserialize, Serialize};
+
+use std::{
+    fs,
+    path::Path,
+    sync::{OnceLock, RwLock},
+};
+
+use crate::{engines::SearchEngine, error::SearchError};
 pub mod modern_widget {
     use super::ModernTheme;
 
@@ -11,10 +20,12 @@ pub mod modern_widget {
     pub type Element<'a, Message> = iced::Element<'a, Message, Renderer>;
     pub type Container<'a, Message> = iced::widget::Container<'a, Message, Renderer>;
     pub type Button<'a, Message> = iced::widget::Button<'a, Message, Renderer>;
+    pub type MouseArea<'a, Message> = iced::widget::mouse_area::MouseArea<'a, Message, Renderer>;
     pub type Text<'a> = iced::widget::Text<'a, Renderer>;
     pub type Row<'a, Message> = iced::widget::Row<'a, Message, Renderer>;
     pub type Column<'a, Message> = iced::widget::Column<'a, Message, Renderer>;
     pub type Scrollable<'a, Message> = iced::widget::Scrollable<'a, Message, Renderer>;
+    pub type Tooltip<'a, Message> = iced::widget::Tooltip<'a, Message, Renderer>;
 }
 
 /// [`RGBAColor`] is a type for human readable conversion
@@ -31,6 +42,35 @@ pub type RGBAColor = (f32, f32, f32, f32);
 /// RGBColor = `(255.0, 255.0, 255.0)` -> Color = `(1.0, 1.0, 1.0)`
 pub type RGBColor = (f32, f32, f32);
 
+/// Builds a [`Color`] out of `0`-`255` channels and a `0`-`100` opacity, clamping the
+/// values out of range. Unlike [`PaletteConversor::from_rgba`] it works in `const`
+/// items, so the bundled palettes are converted at compile time.
+///
+/// # Examples
+///
+/// ```
+/// use iced::Color;
+/// use search::styles::modern::rgba;
+///
+/// const HALF_PURPLE: Color = rgba(127.0, 0.0, 255.0, 50.0);
+///
+/// assert_eq!(HALF_PURPLE, Color::from_rgba(127.0 / 255.0, 0.0, 1.0, 0.5));
+/// assert_eq!(rgba(300.0, -5.0, 255.0, 150.0), Color::from_rgba(1.0, 0.0, 1.0, 1.0));
+/// ```
+pub const fn rgba(r: f32, g: f32, b: f32, a: f32) -> Color {
+    Color {
+        r: (r / 255.0).clamp(0.0, 1.0),
+        g: (g / 255.0).clamp(0.0, 1.0),
+        b: (b / 255.0).clamp(0.0, 1.0),
+        a: (a / 100.0).clamp(0.0, 1.0),
+    }
+}
+
+/// Opaque [`rgba`].
+pub const fn rgb(r: f32, g: f32, b: f32) -> Color {
+    rgba(r, g, b, 100.0)
+}
+
 pub trait PaletteConversor {
     /// Converts a color from `RGBA` format (as four separate floating-point values) to a `Color` object,
     /// using the `PaletteConversor` trait. This function takes in four `f32` values, representing the
@@ -48,6 +88,7 @@ pub trait PaletteConversor {
     ///
     /// A new `Color` object, created from the given RGBA components. The values of `r`, `g`, `b`, and `a`
     /// are divided by 255.0 and 100.0, respectively, to convert them to the proper range for `Color`.
+    /// Values out of range are clamped to the nearest valid one.
     ///
     /// # Examples
     ///
@@ -55,9 +96,9 @@ pub trait PaletteConversor {
     /// use search::styles::modern::PaletteConversor;
     /// use iced::Color;
     ///
-    /// struct Palette {};
+    /// struct Palette;
     ///
-    /// impl PaletteConversor for Palette {};
+    /// impl PaletteConversor for Palette {}
     ///
     /// let color = Palette::from_rgba(127.0, 0.0, 255.0, 50.0);
     /// let should_be_equal = Color {
@@ -67,10 +108,13 @@ pub trait PaletteConversor {
     /// a: 0.5
     /// };
     ///
-    /// assert_eq!(color, should_be_equal)
+    /// assert_eq!(color, should_be_equal);
+    ///
+    /// let clamped = Palette::from_rgba(300.0, -5.0, 255.0, 150.0);
+    /// assert_eq!(clamped, Color::from_rgba(1.0, 0.0, 1.0, 1.0));
     /// ```
     fn from_rgba(r: f32, g: f32, b: f32, a: f32) -> Color {
-        Color::from_rgba(r / 255.0, g / 255.0, b / 255.0, a / 100.0)
+        rgba(r, g, b, a)
     }
 
     /// Converts a color from `RGB` format (as four separate floating-point values) to a `Color` object,
@@ -87,7 +131,8 @@ pub trait PaletteConversor {
     /// # Returns
     ///
     /// A new `Color` object, created from the given RGBA components. The values of `r`, `g`, `b`
-    /// are divided by 255.0 and 100.0, respectively, to convert them to the proper range for `Color`.
+    /// are divided by 255.0 to convert them to the proper range for `Color`, clamping the ones out
+    /// of range.
     ///
     /// # Examples
     ///
@@ -95,9 +140,9 @@ pub trait PaletteConversor {
     /// use search::styles::modern::PaletteConversor;
     /// use iced::Color;
     ///
-    /// struct Palette {};
+    /// struct Palette;
     ///
-    /// impl PaletteConversor for Palette {};
+    /// impl PaletteConversor for Palette {}
     ///
     /// let color = Palette::from_rgb(127.0, 0.0, 255.0);
     /// let should_be_equal = Color {
@@ -107,140 +152,285 @@ pub trait PaletteConversor {
     /// a: 1.0
     /// };
     ///
-    /// assert_eq!(color, should_be_equal)
+    /// assert_eq!(color, should_be_equal);
+    ///
+    /// let clamped = Palette::from_rgb(-20.0, 256.0, 0.0);
+    /// assert_eq!(clamped, Color::from_rgb(0.0, 1.0, 0.0));
     /// ```
     fn from_rgb(r: f32, g: f32, b: f32) -> Color {
-        Color::from_rgba(r / 255.0, g / 255.0, b / 255.0, 1.0)
+        rgb(r, g, b)
     }
 }
 
 trait Properties {
-    const BORDER_RADIUS: f32 = 6.0;
     const BORDER_WIDTH: f32 = 0.0;
+    /// Multiplier applied to the button colors while pressed, `1.0` disables the darkening.
+    const PRESSED_DARKEN: f32 = 0.7;
 }
 
-#[derive(Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ModernTheme {
     #[default]
     Dark,
     Light,
+    /// Dark theme whose accent and engine colors stay distinguishable with
+    /// deuteranopia and protanopia.
+    ColorBlind,
+    /// Black and white theme with outlined controls, for low vision.
+    HighContrast,
+    /// Dark theme on a pure black background, for OLED screens.
+    ///
+    /// The input, history, rule and scrollbar are lifted to greys that still stand
+    /// out on it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iced::Color;
+    /// use search::styles::modern::ModernTheme;
+    ///
+    /// let palette = ModernTheme::Amoled.palette();
+    ///
+    /// assert_eq!(palette.app.background(), Color::BLACK);
+    /// assert_ne!(palette.inputs.background(), Color::BLACK);
+    /// // The rule and the scroller are drawn with the placeholder color, the track
+    /// // with the secondary one.
+    /// assert_ne!(palette.inputs.placeholder_text(), palette.buttons.secondary());
+    /// assert_ne!(palette.inputs.placeholder_text(), palette.inputs.background());
+    /// ```
+    Amoled,
+    /// Theme read from a `theme.toml` file, see [`ModernTheme::custom`].
+    Custom,
 }
 
 impl Properties for ModernTheme {}
 
 #[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ButtonsPalette {
-    text: RGBAColor,
-    principal: RGBAColor,
-    secondary: RGBAColor,
-    tag: RGBAColor,
+    #[serde(with = "hex")]
+    text: Color,
+    #[serde(with = "hex")]
+    principal: Color,
+    #[serde(with = "hex")]
+    secondary: Color,
+    #[serde(with = "hex")]
+    tag: Color,
+    #[serde(with = "hex", default = "default_danger")]
+    danger: Color,
+}
+
+/// Destructive button color of palettes saved before it was configurable.
+fn default_danger() -> Color {
+    rgb(232.0, 93.0, 93.0)
 }
 
 #[derive(Default)]
 pub enum ModernButton {
+    /// Button filled with the accent, its text black or white to read over it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iced::widget::button::StyleSheet;
+    /// use search::styles::modern::{ModernButton, ModernTheme};
+    ///
+    /// for theme in ModernTheme::ALL {
+    ///     let active = theme.active(&ModernButton::Principal);
+    ///     let pressed = theme.pressed(&ModernButton::Principal);
+    ///
+    ///     assert_ne!(active.text_color, iced::Color::from_rgb8(255, 110, 1));
+    ///     assert_eq!(pressed.text_color.r, active.text_color.r * 0.7);
+    /// }
+    /// ```
     #[default]
     Principal,
     Secondary,
     Text,
-    Tag((f32, f32, f32)),
+    /// Button filled with the color of an engine, or the tag color without one.
+    Tag(Option<SearchEngine>),
+    /// Tag of the engine the next search goes to, outlined with the accent or label color.
+    SelectedTag(SearchEngine),
+    /// Text button whose content is only visible while hovered.
+    Reveal,
+    /// Destructive action, tinted with the danger color and filled with it on hover.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iced::widget::button::StyleSheet;
+    /// use search::styles::modern::{ModernButton, ModernTheme};
+    ///
+    /// for theme in ModernTheme::ALL {
+    ///     let danger = theme.palette().buttons.danger();
+    ///
+    ///     assert_eq!(theme.active(&ModernButton::Danger).text_color, danger);
+    ///     assert_eq!(theme.hovered(&ModernButton::Danger).background, Some(danger.into()));
+    ///     assert_ne!(theme.pressed(&ModernButton::Danger).background, Some(danger.into()));
+    /// }
+    /// ```
+    Danger,
 }
 
 impl PaletteConversor for ButtonsPalette {}
 
 impl ButtonsPalette {
     pub fn label(&self) -> Color {
-        let (r, g, b, a) = self.text;
-        Self::from_rgba(r, g, b, a)
+        self.text
     }
 
     pub fn primary(&self) -> Color {
-        let (r, g, b, a) = self.principal;
-        Self::from_rgba(r, g, b, a)
+        self.principal
     }
 
     pub fn secondary(&self) -> Color {
-        let (r, g, b, a) = self.secondary;
-        Self::from_rgba(r, g, b, a)
+        self.secondary
     }
 
     pub fn text(&self) -> Color {
-        let (r, g, b, a) = self.text;
-        Self::from_rgba(r, g, b, a)
+        self.text
     }
 
     pub fn tag(&self) -> Color {
-        let (r, g, b, a) = self.tag;
-        Self::from_rgba(r, g, b, a)
+        self.tag
+    }
+
+    pub fn danger(&self) -> Color {
+        self.danger
+    }
+}
+
+/// Colors of the engine tags and badges, so each theme can pick ones its labels
+/// read over.
+///
+/// Theme files without an `[engines]` table take the colors of the dark theme.
+///
+/// # Examples
+///
+/// ```
+/// use search::engines::SearchEngine;
+/// use search::styles::modern::ModernTheme;
+///
+/// let engine = SearchEngine::StackOverflow;
+/// let dark = &ModernTheme::Dark.palette().engines;
+/// let light = &ModernTheme::Light.palette().engines;
+///
+/// assert_ne!(dark.color(engine), light.color(engine));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnginesPalette {
+    #[serde(with = "hex")]
+    pub stack_overflow: Color,
+    #[serde(with = "hex")]
+    pub stack_exchange: Color,
+    #[serde(with = "hex")]
+    pub duck_duck_go: Color,
+}
+
+impl EnginesPalette {
+    pub fn color(&self, engine: SearchEngine) -> Color {
+        match engine {
+            SearchEngine::StackOverflow => self.stack_overflow,
+            SearchEngine::StackExchange => self.stack_exchange,
+            SearchEngine::DuckDuckGo => self.duck_duck_go,
+        }
+    }
+}
+
+impl Default for EnginesPalette {
+    fn default() -> Self {
+        ModernPalette::DARK.engines
     }
 }
 
 #[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InputPalette {
-    background: RGBAColor,
-    border_color: RGBAColor,
-    icon_color: RGBAColor,
-    placeholder_text: RGBAColor,
-    text: RGBAColor,
-    disabled_color: RGBAColor,
-    disabled: RGBAColor,
+    #[serde(with = "hex")]
+    background: Color,
+    #[serde(with = "hex")]
+    border_color: Color,
+    #[serde(with = "hex")]
+    icon_color: Color,
+    #[serde(with = "hex")]
+    placeholder_text: Color,
+    /// Opacity of the placeholder, between `0` and `100`. Kept apart from
+    /// `placeholder_text` so each theme can tune the contrast of the hint alone.
+    #[serde(default = "default_placeholder_alpha")]
+    placeholder_alpha: f32,
+    #[serde(with = "hex")]
+    text: Color,
+    #[serde(with = "hex")]
+    disabled_color: Color,
+    #[serde(with = "hex")]
+    disabled: Color,
 }
 
 impl PaletteConversor for InputPalette {}
 
+/// Placeholder opacity used by palettes saved before it was configurable.
+fn default_placeholder_alpha() -> f32 {
+    60.0
+}
+
 impl InputPalette {
     pub fn background(&self) -> Color {
-        let (r, g, b, a) = self.background;
-        Self::from_rgba(r, g, b, a)
+        self.background
     }
 
     pub fn border_color(&self) -> Color {
-        let (r, g, b, a) = self.border_color;
-        Self::from_rgba(r, g, b, a)
+        self.border_color
     }
 
     pub fn icon_color(&self) -> Color {
-        let (r, g, b, a) = self.icon_color;
-        Self::from_rgba(r, g, b, a)
+        self.icon_color
     }
 
     pub fn placeholder_text(&self) -> Color {
-        let (r, g, b, a) = self.placeholder_text;
-        Self::from_rgba(r, g, b, a)
+        self.placeholder_text
+    }
+
+    /// Color of the input placeholder, `placeholder_text` with `placeholder_alpha`.
+    pub fn placeholder(&self) -> Color {
+        Color {
+            a: (self.placeholder_alpha / 100.0).clamp(0.0, 1.0),
+            ..self.placeholder_text
+        }
     }
 
     pub fn text(&self) -> Color {
-        let (r, g, b, a) = self.text;
-        Self::from_rgba(r, g, b, a)
+        self.text
     }
 
     pub fn disabled_color(&self) -> Color {
-        let (r, g, b, a) = self.disabled_color;
-        Self::from_rgba(r, g, b, a)
+        self.disabled_color
     }
 
     pub fn disabled(&self) -> Color {
-        let (r, g, b, a) = self.disabled;
-        Self::from_rgba(r, g, b, a)
+        self.disabled
     }
 }
 
 #[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ApplicationPalette {
-    background: RGBAColor,
-    text: RGBAColor,
+    #[serde(with = "hex")]
+    background: Color,
+    #[serde(with = "hex")]
+    text: Color,
 }
 
 impl PaletteConversor for ApplicationPalette {}
 
 impl ApplicationPalette {
     pub fn background(&self) -> Color {
-        let (r, g, b, a) = self.background;
-        Self::from_rgba(r, g, b, a)
+        self.background
     }
 
     pub fn text(&self) -> Color {
-        let (r, g, b, a) = self.text;
-        Self::from_rgba(r, g, b, a)
+        self.text
     }
 }
 
@@ -250,29 +440,88 @@ pub enum ModernContainer {
     Default,
     Historial,
     Input,
+    /// [`ModernContainer::Input`] outlined with the danger color, flashed when there's
+    /// nothing to search.
+    InputError,
     Line,
+    /// Small colored dot, neutral when there's no color.
+    Badge(Option<RGBColor>),
+    /// Floating panel drawn over the rest of the app.
+    Popover,
+    /// Round engine placeholder, its text contrasts with the color.
+    Avatar(RGBColor),
+    /// Dimmed layer covering the app behind a modal.
+    Backdrop,
+    /// Card holding the modal content.
+    Modal,
+    /// Dark card of a tooltip, outlined so it stands out on dark backgrounds too.
+    Tooltip,
+    /// Row picked with the keyboard, outlined with the accent.
+    Highlight,
+    /// Card around suggestions and results, always outlined with the container
+    /// border, or the input one when the theme sets none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iced::widget::container::StyleSheet;
+    /// use search::styles::modern::{ModernContainer, ModernTheme};
+    ///
+    /// for theme in ModernTheme::ALL {
+    ///     let card = theme.appearance(&ModernContainer::Card);
+    ///
+    ///     assert!(card.border_width >= 1.0);
+    ///     assert_ne!(card.border_color, iced::Color::TRANSPARENT);
+    /// }
+    /// ```
+    Card,
 }
 #[derive(Default, Clone, Copy)]
 pub enum ModernColor {
     #[default]
     Default,
+    /// [`ContainerPalette::muted_text`] of the theme.
+    Muted,
+    /// [`ContainerPalette::empty_text`] of the theme.
+    Empty,
+    /// The accent set with [`set_accent`], or the primary button color.
+    Accent,
     Custom(f32, f32, f32),
+    /// Custom color with its opacity, from `0` to `100`.
+    CustomAlpha(f32, f32, f32, f32),
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ContainerPalette {
-    text: RGBAColor,
+    #[serde(with = "hex")]
+    text: Color,
     border_radius: f32,
     border_width: f32,
-    border_color: Option<RGBAColor>,
-    background: Option<RGBAColor>,
+    #[serde(with = "hex::option", default, skip_serializing_if = "Option::is_none")]
+    border_color: Option<Color>,
+    #[serde(with = "hex::option", default, skip_serializing_if = "Option::is_none")]
+    background: Option<Color>,
+    #[serde(with = "hex", default = "default_muted_text")]
+    muted_text: Color,
+    #[serde(with = "hex", default = "default_empty_text")]
+    empty_text: Color,
 }
 
 impl PaletteConversor for ContainerPalette {}
 
+/// Muted text of palettes saved before it was configurable.
+fn default_muted_text() -> Color {
+    rgb(160.0, 160.0, 160.0)
+}
+
+/// Empty state text of palettes saved before it was configurable.
+fn default_empty_text() -> Color {
+    rgb(82.0, 81.0, 90.0)
+}
+
 impl ContainerPalette {
     pub fn text(&self) -> Color {
-        let (r, g, b, a) = self.text;
-        Self::from_rgba(r, g, b, a)
+        self.text
     }
 
     pub fn border_radius(&self) -> f32 {
@@ -284,45 +533,151 @@ impl ContainerPalette {
     }
 
     pub fn border_color(&self) -> Option<Color> {
-        if let Some((r, g, b, a)) = self.border_color {
-            return Some(Self::from_rgba(r, g, b, a));
-        }
-        None
+        self.border_color
     }
 
     pub fn background(&self) -> Option<Background> {
-        if let Some((r, g, b, a)) = self.background {
-            return Some(Self::from_rgba(r, g, b, a).into());
-        }
-        None
+        self.background.map(Background::from)
+    }
+
+    /// Secondary text, like the history times, counts and row icons.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::styles::modern::ModernTheme;
+    ///
+    /// let dark = &ModernTheme::Dark.palette().container;
+    /// let light = &ModernTheme::Light.palette().container;
+    ///
+    /// assert_ne!(dark.muted_text(), light.muted_text());
+    /// assert_ne!(dark.empty_text(), light.empty_text());
+    /// ```
+    pub fn muted_text(&self) -> Color {
+        self.muted_text
+    }
+
+    /// Text of the empty history, fainter than [`ContainerPalette::muted_text`].
+    pub fn empty_text(&self) -> Color {
+        self.empty_text
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TogglerPalette {
-    background: RGBAColor,
-    foreground: RGBAColor,
+    #[serde(with = "hex")]
+    background: Color,
+    #[serde(with = "hex")]
+    foreground: Color,
 }
 
 impl TogglerPalette {
     pub fn background(&self) -> Color {
-        let (r, g, b, a) = self.background;
-        Self::from_rgba(r, g, b, a)
+        self.background
     }
 
     pub fn foreground(&self) -> Color {
-        let (r, g, b, a) = self.foreground;
-        Self::from_rgba(r, g, b, a)
+        self.foreground
     }
 }
 
 impl PaletteConversor for TogglerPalette {}
 
+/// Corner radius of each kind of widget, in pixels.
+///
+/// Theme files without a `[radii]` table keep the rounded look of the bundled themes.
+///
+/// # Examples
+///
+/// ```
+/// use search::styles::modern::{ModernPalette, ModernTheme, RadiiPalette};
+///
+/// let exported = ModernTheme::Dark.palette().to_toml();
+/// let without_radii = exported.split("[radii]").next().unwrap();
+///
+/// let palette = ModernPalette::from_toml(without_radii).unwrap();
+/// assert_eq!(palette.radii, RadiiPalette::default());
+///
+/// let sharp = exported.replace("input = 100.0", "input = 4.0");
+/// assert_eq!(ModernPalette::from_toml(&sharp).unwrap().radii.input, 4.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RadiiPalette {
+    /// Buttons and engine tags.
+    pub button: f32,
+    /// The search bar and every text input.
+    pub input: f32,
+    pub popover: f32,
+    pub modal: f32,
+    pub tooltip: f32,
+}
+
+impl RadiiPalette {
+    const ROUNDED: Self = Self {
+        button: 100.0,
+        input: 100.0,
+        popover: 12.0,
+        modal: 20.0,
+        tooltip: 6.0,
+    };
+}
+
+impl Default for RadiiPalette {
+    fn default() -> Self {
+        Self::ROUNDED
+    }
+}
+
+/// Colors of the scrollbars, the thumb being the part dragged along the track.
+///
+/// Theme files without a `[scrollbar]` table take the colors of the dark theme.
+///
+/// # Examples
+///
+/// ```
+/// use search::styles::modern::ModernTheme;
+///
+/// for theme in ModernTheme::ALL {
+///     let scrollbar = &theme.palette().scrollbar;
+///     let apart = |a: iced::Color, b: iced::Color| (a.r - b.r).abs() + (a.g - b.g).abs() > 0.2;
+///
+///     assert!(apart(scrollbar.thumb, scrollbar.track), "{theme:?}");
+///     assert!(apart(scrollbar.hovered_thumb, scrollbar.hovered_track), "{theme:?}");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScrollbarPalette {
+    #[serde(with = "hex")]
+    pub track: Color,
+    #[serde(with = "hex")]
+    pub thumb: Color,
+    #[serde(with = "hex")]
+    pub hovered_track: Color,
+    #[serde(with = "hex")]
+    pub hovered_thumb: Color,
+}
+
+impl Default for ScrollbarPalette {
+    fn default() -> Self {
+        ModernPalette::DARK.scrollbar
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ModernPalette {
     pub buttons: ButtonsPalette,
     pub inputs: InputPalette,
     pub container: ContainerPalette,
     pub toggler: TogglerPalette,
     pub app: ApplicationPalette,
+    #[serde(default)]
+    pub radii: RadiiPalette,
+    #[serde(default)]
+    pub scrollbar: ScrollbarPalette,
+    #[serde(default)]
+    pub engines: EnginesPalette,
 }
 
 impl PaletteConversor for ModernTheme {}
@@ -330,77 +685,420 @@ impl PaletteConversor for ModernTheme {}
 impl ModernPalette {
     const DARK: Self = Self {
         buttons: ButtonsPalette {
-            text: (255.0, 255.0, 255.0, 100.0),
-            principal: (253.0, 213.0, 193.0, 100.0),
-            secondary: (82.0, 89.0, 96.0, 100.0),
-            tag: (82.0, 89.0, 96.0, 100.0),
+            text: rgba(255.0, 255.0, 255.0, 100.0),
+            principal: rgba(253.0, 213.0, 193.0, 100.0),
+            secondary: rgba(82.0, 89.0, 96.0, 100.0),
+            tag: rgba(82.0, 89.0, 96.0, 100.0),
+            danger: rgba(232.0, 93.0, 93.0, 100.0),
         },
         inputs: InputPalette {
-            background: (39.0, 38.0, 47.0, 100.0),
-            border_color: (60.0, 60.0, 60.0, 30.0),
-            icon_color: (90.0, 90.0, 90.0, 100.0),
-            placeholder_text: (100.0, 100.0, 100.0, 60.0),
-            text: (233.0, 233.0, 233.0, 100.0),
-            disabled_color: (60.0, 60.0, 60.0, 60.0),
-            disabled: (60.0, 60.0, 60.0, 60.0),
+            background: rgba(39.0, 38.0, 47.0, 100.0),
+            border_color: rgba(60.0, 60.0, 60.0, 30.0),
+            icon_color: rgba(90.0, 90.0, 90.0, 100.0),
+            placeholder_text: rgba(100.0, 100.0, 100.0, 60.0),
+            placeholder_alpha: 75.0,
+            text: rgba(233.0, 233.0, 233.0, 100.0),
+            disabled_color: rgba(60.0, 60.0, 60.0, 60.0),
+            disabled: rgba(60.0, 60.0, 60.0, 60.0),
         },
         container: ContainerPalette {
-            text: (90.0, 90.0, 90.0, 100.0),
-            border_radius: 6.0,
+            text: rgba(90.0, 90.0, 90.0, 100.0),
+            border_radius: 35.0,
             border_width: 0.0,
             border_color: None,
-            background: Some((60.0, 60.0, 60.0, 30.0)),
+            background: Some(rgba(60.0, 60.0, 60.0, 30.0)),
+            muted_text: rgba(160.0, 160.0, 160.0, 100.0),
+            empty_text: rgba(82.0, 81.0, 90.0, 100.0),
         },
         toggler: TogglerPalette {
-            background: (33.0, 35.0, 37.0, 100.0),
-            foreground: (60.0, 60.0, 60.0, 100.0),
+            background: rgba(33.0, 35.0, 37.0, 100.0),
+            foreground: rgba(60.0, 60.0, 60.0, 100.0),
         },
         app: ApplicationPalette {
-            background: (31.0, 30.0, 37.0, 100.0),
-            text: (250.0, 250.0, 242.0, 100.0),
+            background: rgba(31.0, 30.0, 37.0, 100.0),
+            text: rgba(250.0, 250.0, 242.0, 100.0),
         },
+        radii: RadiiPalette::ROUNDED,
+        scrollbar: ScrollbarPalette {
+            track: rgba(82.0, 89.0, 96.0, 100.0),
+            thumb: rgba(150.0, 150.0, 150.0, 100.0),
+            hovered_track: rgba(100.0, 100.0, 100.0, 100.0),
+            hovered_thumb: rgba(253.0, 213.0, 193.0, 100.0),
+        },
+        engines: EnginesPalette {
+            stack_overflow: rgba(252.0, 187.0, 150.0, 100.0),
+            stack_exchange: rgba(175.0, 197.0, 226.0, 100.0),
+            duck_duck_go: rgba(222.0, 88.0, 51.0, 100.0),
+        },
+    };
+    /// [`ModernPalette::DARK`] with its accent taken from the Okabe-Ito palette.
+    const COLOR_BLIND: Self = Self {
+        buttons: ButtonsPalette {
+            text: rgba(255.0, 255.0, 255.0, 100.0),
+            principal: rgba(86.0, 180.0, 233.0, 100.0),
+            secondary: rgba(82.0, 89.0, 96.0, 100.0),
+            tag: rgba(82.0, 89.0, 96.0, 100.0),
+            danger: rgba(213.0, 94.0, 0.0, 100.0),
+        },
+        scrollbar: ScrollbarPalette {
+            hovered_thumb: rgba(86.0, 180.0, 233.0, 100.0),
+            ..Self::DARK.scrollbar
+        },
+        // Okabe-Ito colors, which stay apart under the usual color vision deficiencies.
+        engines: EnginesPalette {
+            stack_overflow: rgba(230.0, 159.0, 0.0, 100.0),
+            stack_exchange: rgba(0.0, 114.0, 178.0, 100.0),
+            duck_duck_go: rgba(213.0, 94.0, 0.0, 100.0),
+        },
+        ..Self::DARK
+    };
+    /// Pure black and white, with a yellow accent and every border drawn.
+    const HIGH_CONTRAST: Self = Self {
+        buttons: ButtonsPalette {
+            text: rgba(255.0, 255.0, 255.0, 100.0),
+            principal: rgba(255.0, 214.0, 0.0, 100.0),
+            secondary: rgba(0.0, 0.0, 0.0, 100.0),
+            tag: rgba(0.0, 0.0, 0.0, 100.0),
+            danger: rgba(255.0, 90.0, 90.0, 100.0),
+        },
+        inputs: InputPalette {
+            background: rgba(0.0, 0.0, 0.0, 100.0),
+            border_color: rgba(255.0, 255.0, 255.0, 100.0),
+            icon_color: rgba(255.0, 255.0, 255.0, 100.0),
+            placeholder_text: rgba(255.0, 255.0, 255.0, 100.0),
+            placeholder_alpha: 80.0,
+            text: rgba(255.0, 255.0, 255.0, 100.0),
+            disabled_color: rgba(160.0, 160.0, 160.0, 100.0),
+            disabled: rgba(160.0, 160.0, 160.0, 100.0),
+        },
+        container: ContainerPalette {
+            text: rgba(255.0, 255.0, 255.0, 100.0),
+            border_radius: 35.0,
+            border_width: 2.0,
+            border_color: Some(rgba(255.0, 255.0, 255.0, 100.0)),
+            background: Some(rgba(0.0, 0.0, 0.0, 100.0)),
+            muted_text: rgba(255.0, 255.0, 255.0, 100.0),
+            empty_text: rgba(200.0, 200.0, 200.0, 100.0),
+        },
+        toggler: TogglerPalette {
+            background: rgba(255.0, 255.0, 255.0, 100.0),
+            foreground: rgba(0.0, 0.0, 0.0, 100.0),
+        },
+        app: ApplicationPalette {
+            background: rgba(0.0, 0.0, 0.0, 100.0),
+            text: rgba(255.0, 255.0, 255.0, 100.0),
+        },
+        radii: RadiiPalette::ROUNDED,
+        scrollbar: ScrollbarPalette {
+            track: rgba(0.0, 0.0, 0.0, 100.0),
+            thumb: rgba(255.0, 255.0, 255.0, 100.0),
+            hovered_track: rgba(80.0, 80.0, 80.0, 100.0),
+            hovered_thumb: rgba(255.0, 214.0, 0.0, 100.0),
+        },
+        engines: EnginesPalette {
+            stack_overflow: rgba(252.0, 187.0, 150.0, 100.0),
+            stack_exchange: rgba(175.0, 197.0, 226.0, 100.0),
+            duck_duck_go: rgba(222.0, 88.0, 51.0, 100.0),
+        },
+    };
+    /// [`ModernPalette::DARK`] on a true black background, with lifted greys on top.
+    const AMOLED: Self = Self {
+        inputs: InputPalette {
+            background: rgba(22.0, 22.0, 26.0, 100.0),
+            border_color: rgba(70.0, 70.0, 70.0, 40.0),
+            icon_color: rgba(110.0, 110.0, 110.0, 100.0),
+            placeholder_text: rgba(120.0, 120.0, 120.0, 70.0),
+            placeholder_alpha: 75.0,
+            text: rgba(233.0, 233.0, 233.0, 100.0),
+            disabled_color: rgba(60.0, 60.0, 60.0, 60.0),
+            disabled: rgba(60.0, 60.0, 60.0, 60.0),
+        },
+        container: ContainerPalette {
+            text: rgba(110.0, 110.0, 110.0, 100.0),
+            border_radius: 35.0,
+            border_width: 0.0,
+            border_color: None,
+            background: Some(rgba(22.0, 22.0, 26.0, 100.0)),
+            muted_text: rgba(160.0, 160.0, 160.0, 100.0),
+            empty_text: rgba(82.0, 81.0, 90.0, 100.0),
+        },
+        toggler: TogglerPalette {
+            background: rgba(22.0, 22.0, 26.0, 100.0),
+            foreground: rgba(70.0, 70.0, 70.0, 100.0),
+        },
+        app: ApplicationPalette {
+            background: rgba(0.0, 0.0, 0.0, 100.0),
+            text: rgba(250.0, 250.0, 242.0, 100.0),
+        },
+        scrollbar: ScrollbarPalette {
+            track: rgba(40.0, 40.0, 46.0, 100.0),
+            thumb: rgba(120.0, 120.0, 120.0, 100.0),
+            hovered_track: rgba(60.0, 60.0, 66.0, 100.0),
+            hovered_thumb: rgba(253.0, 213.0, 193.0, 100.0),
+        },
+        ..Self::DARK
     };
     const LIGHT: Self = Self {
         buttons: ButtonsPalette {
-            text: (255.0, 255.0, 255.0, 100.0),
-            principal: (51.0, 88.0, 219.0, 100.0),
-            secondary: (82.0, 89.0, 96.0, 100.0),
-            tag: (51.0, 245.0, 106.0, 100.0),
+            text: rgba(255.0, 255.0, 255.0, 100.0),
+            principal: rgba(51.0, 88.0, 219.0, 100.0),
+            secondary: rgba(82.0, 89.0, 96.0, 100.0),
+            tag: rgba(120.0, 124.0, 130.0, 100.0),
+            danger: rgba(200.0, 40.0, 40.0, 100.0),
         },
         inputs: InputPalette {
-            background: (250.0, 250.0, 242.0, 100.0),
-            border_color: (60.0, 60.0, 60.0, 30.0),
-            icon_color: (90.0, 90.0, 90.0, 100.0),
-            placeholder_text: (60.0, 60.0, 60.0, 60.0),
-            text: (90.0, 90.0, 90.0, 100.0),
-            disabled_color: (60.0, 60.0, 60.0, 60.0),
-            disabled: (60.0, 60.0, 60.0, 60.0),
+            background: rgba(250.0, 250.0, 242.0, 100.0),
+            border_color: rgba(60.0, 60.0, 60.0, 30.0),
+            icon_color: rgba(90.0, 90.0, 90.0, 100.0),
+            placeholder_text: rgba(60.0, 60.0, 60.0, 60.0),
+            placeholder_alpha: 45.0,
+            text: rgba(90.0, 90.0, 90.0, 100.0),
+            disabled_color: rgba(60.0, 60.0, 60.0, 60.0),
+            disabled: rgba(60.0, 60.0, 60.0, 60.0),
         },
         container: ContainerPalette {
-            text: (90.0, 90.0, 90.0, 100.0),
-            border_radius: 6.0,
+            text: rgba(90.0, 90.0, 90.0, 100.0),
+            border_radius: 35.0,
             border_width: 0.0,
             border_color: None,
-            background: Some((60.0, 60.0, 60.0, 30.0)),
+            background: Some(rgba(60.0, 60.0, 60.0, 30.0)),
+            muted_text: rgba(110.0, 110.0, 110.0, 100.0),
+            empty_text: rgba(150.0, 150.0, 158.0, 100.0),
         },
         toggler: TogglerPalette {
-            background: (250.0, 250.0, 250.0, 100.0),
-            foreground: (60.0, 60.0, 60.0, 30.0),
+            background: rgba(250.0, 250.0, 250.0, 100.0),
+            foreground: rgba(60.0, 60.0, 60.0, 30.0),
         },
         app: ApplicationPalette {
-            text: (33.0, 35.0, 37.0, 100.0),
-            background: (250.0, 250.0, 242.0, 100.0),
+            text: rgba(33.0, 35.0, 37.0, 100.0),
+            background: rgba(250.0, 250.0, 242.0, 100.0),
+        },
+        radii: RadiiPalette::ROUNDED,
+        scrollbar: ScrollbarPalette {
+            track: rgba(222.0, 222.0, 216.0, 100.0),
+            thumb: rgba(140.0, 140.0, 140.0, 100.0),
+            hovered_track: rgba(200.0, 200.0, 194.0, 100.0),
+            hovered_thumb: rgba(51.0, 88.0, 219.0, 100.0),
+        },
+        engines: EnginesPalette {
+            stack_overflow: rgba(209.0, 105.0, 32.0, 100.0),
+            stack_exchange: rgba(54.0, 111.0, 171.0, 100.0),
+            duck_duck_go: rgba(190.0, 70.0, 38.0, 100.0),
         },
     };
 }
 
+impl ModernPalette {
+    /// Serializes the palette as a TOML document, with every color as an hex string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::styles::modern::{ModernPalette, ModernTheme};
+    ///
+    /// let palette = ModernTheme::Dark.palette();
+    /// let exported = palette.to_toml();
+    ///
+    /// assert!(exported.contains("#1F1E25FF"));
+    /// assert_eq!(&ModernPalette::from_toml(&exported).unwrap(), palette);
+    /// ```
+    pub fn to_toml(&self) -> String {
+        toml::to_string_pretty(self).expect("palettes are always serializable")
+    }
+
+    /// Parses a palette written by [`ModernPalette::to_toml`].
+    pub fn from_toml(contents: &str) -> crate::error::Result<Self> {
+        toml::from_str(contents).map_err(|err| SearchError::parse("palette", err))
+    }
+}
+
+/// Palette of [`ModernTheme::Custom`], set once on launch.
+static CUSTOM_PALETTE: OnceLock<ModernPalette> = OnceLock::new();
+
+/// Accent picked on the settings screen, replacing the principal color of every theme.
+static ACCENT: RwLock<Option<RGBColor>> = RwLock::new(None);
+
+/// Overrides the accent of every theme, `None` goes back to the palette one.
+///
+/// # Examples
+///
+/// ```
+/// use search::styles::modern::{accent, set_accent};
+///
+/// set_accent(Some((255.0, 0.0, 0.0)));
+/// assert_eq!(accent(), Some(iced::Color::from_rgb(1.0, 0.0, 0.0)));
+///
+/// set_accent(None);
+/// assert_eq!(accent(), None);
+/// ```
+pub fn set_accent(color: Option<RGBColor>) {
+    *ACCENT.write().unwrap_or_else(|err| err.into_inner()) = color;
+}
+
+/// The accent set with [`set_accent`], if any.
+pub fn accent() -> Option<Color> {
+    let accent = *ACCENT.read().unwrap_or_else(|err| err.into_inner());
+    accent.map(|(r, g, b)| ButtonsPalette::from_rgb(r, g, b))
+}
+
+/// Reads a palette file, as exported from the settings screen.
+///
+/// # Examples
+///
+/// ```
+/// use search::styles::modern::{palette_from_config, ModernTheme};
+///
+/// let path = std::env::temp_dir().join("capy-search-palette.toml");
+/// std::fs::write(&path, ModernTheme::Light.palette().to_toml()).unwrap();
+///
+/// assert_eq!(&palette_from_config(&path).unwrap(), ModernTheme::Light.palette());
+/// assert!(palette_from_config(&path.with_extension("missing")).is_err());
+/// ```
+pub fn palette_from_config(path: &Path) -> crate::error::Result<ModernPalette> {
+    ModernPalette::from_toml(&fs::read_to_string(path)?)
+}
+
+/// Black or white, whichever reads better over the given color.
+fn contrast(r: f32, g: f32, b: f32) -> Color {
+    let luma = 0.299 * r + 0.587 * g + 0.114 * b;
+    if luma > 150.0 {
+        Color::from_rgb8(33, 35, 37)
+    } else {
+        Color::WHITE
+    }
+}
+
+/// Conversion between [`Color`] and `#RRGGBBAA` hex strings.
+///
+/// The alpha byte is mapped from the `0..=100` range, so exporting and importing
+/// a palette with integer components gives back the same values.
+pub(crate) mod hex {
+    use super::rgba;
+    use iced::Color;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn to_hex(color: Color) -> String {
+        let [r, g, b, _] = color.into_rgba8();
+        let alpha = (color.a * 100.0).round();
+        format!("#{r:02X}{g:02X}{b:02X}{:02X}", (alpha * 2.55).round() as u8)
+    }
+
+    pub fn from_hex(hex: &str) -> Option<Color> {
+        let digits = hex.strip_prefix('#')?;
+        if !matches!(digits.len(), 6 | 8) || !digits.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+        let alpha = match digits.len() {
+            8 => (channel(6)? as f32 / 2.55).round(),
+            _ => 100.0,
+        };
+        Some(rgba(
+            channel(0)? as f32,
+            channel(2)? as f32,
+            channel(4)? as f32,
+            alpha,
+        ))
+    }
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(*color))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        from_hex(&hex).ok_or_else(|| D::Error::custom(format!("invalid hex color `{hex}`")))
+    }
+
+    pub mod option {
+        use iced::Color;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            color: &Option<Color>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match color {
+                Some(color) => super::serialize(color, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Color>, D::Error> {
+            #[derive(Deserialize)]
+            struct Hex(#[serde(with = "super")] Color);
+
+            Ok(Option::<Hex>::deserialize(deserializer)?.map(|Hex(color)| color))
+        }
+    }
+}
+
 impl ModernTheme {
-    fn palette(&self) -> ModernPalette {
+    pub const ALL: [ModernTheme; 5] = [
+        ModernTheme::Dark,
+        ModernTheme::Light,
+        ModernTheme::ColorBlind,
+        ModernTheme::HighContrast,
+        ModernTheme::Amoled,
+    ];
+
+    /// Sets the palette of [`ModernTheme::Custom`] and returns it.
+    ///
+    /// Only the first palette is kept, the theme can't change once it's shown.
+    pub fn custom(palette: ModernPalette) -> ModernTheme {
+        let _ = CUSTOM_PALETTE.set(palette);
+        ModernTheme::Custom
+    }
+
+    /// Themes to pick from, the custom one only when it was loaded.
+    pub fn available() -> Vec<ModernTheme> {
+        let custom = CUSTOM_PALETTE.get().map(|_| ModernTheme::Custom);
+        ModernTheme::ALL.into_iter().chain(custom).collect()
+    }
+
+    /// Name shown on the theme picker.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ModernTheme::Dark => "Dark",
+            ModernTheme::Light => "Light",
+            ModernTheme::ColorBlind => "Color blind",
+            ModernTheme::HighContrast => "High contrast",
+            ModernTheme::Amoled => "AMOLED",
+            ModernTheme::Custom => "Custom",
+        }
+    }
+
+    /// Colors of the theme, the custom theme falls back to the dark ones until loaded.
+    ///
+    /// Every style query asks for it, so it borrows the palette instead of building
+    /// one each time.
+    pub fn palette(&self) -> &'static ModernPalette {
         match self {
-            ModernTheme::Dark => ModernPalette::DARK,
-            ModernTheme::Light => ModernPalette::LIGHT,
+            ModernTheme::Dark => &ModernPalette::DARK,
+            ModernTheme::Light => &ModernPalette::LIGHT,
+            ModernTheme::ColorBlind => &ModernPalette::COLOR_BLIND,
+            ModernTheme::HighContrast => &ModernPalette::HIGH_CONTRAST,
+            ModernTheme::Amoled => &ModernPalette::AMOLED,
+            ModernTheme::Custom => CUSTOM_PALETTE.get().unwrap_or(&ModernPalette::DARK),
         }
     }
+
+    /// Width of the outline of text inputs and secondary buttons.
+    fn outline(&self) -> f32 {
+        match self {
+            ModernTheme::HighContrast => 2.0,
+            _ => Self::BORDER_WIDTH,
+        }
+    }
+
+    /// Theme picked after this one by the theme button.
+    pub fn next(&self) -> ModernTheme {
+        let themes = ModernTheme::available();
+        let current = themes.iter().position(|theme| theme == self).unwrap_or(0);
+        themes[(current + 1) % themes.len()]
+    }
 }
 
 impl application::StyleSheet for ModernTheme {
@@ -422,9 +1120,21 @@ impl text::StyleSheet for ModernTheme {
             ModernColor::Default => text::Appearance {
                 ..Default::default()
             },
+            ModernColor::Muted => text::Appearance {
+                color: Some(self.palette().container.muted_text()),
+            },
+            ModernColor::Empty => text::Appearance {
+                color: Some(self.palette().container.empty_text()),
+            },
+            ModernColor::Accent => text::Appearance {
+                color: Some(accent().unwrap_or_else(|| self.palette().buttons.primary())),
+            },
             ModernColor::Custom(r, g, b) => text::Appearance {
                 color: Some(Self::from_rgba(r, g, b, 100.0)),
             },
+            ModernColor::CustomAlpha(r, g, b, a) => text::Appearance {
+                color: Some(Self::from_rgba(r, g, b, a)),
+            },
         }
     }
 }
@@ -434,42 +1144,90 @@ impl button::StyleSheet for ModernTheme {
 
     fn active(&self, style: &Self::Style) -> button::Appearance {
         match style {
-            ModernButton::Principal => button::Appearance {
-                background: self.palette().buttons.primary().into(),
-                border_radius: 100.0,
-                border_width: Self::BORDER_WIDTH,
-                border_color: Color::TRANSPARENT,
-                text_color: color!(255, 110, 1),
-                ..Default::default()
-            },
+            ModernButton::Principal => {
+                let background = accent().unwrap_or_else(|| self.palette().buttons.primary());
+                button::Appearance {
+                    background: background.into(),
+                    border_radius: self.palette().radii.button,
+                    border_width: Self::BORDER_WIDTH,
+                    border_color: Color::TRANSPARENT,
+                    text_color: match self {
+                        ModernTheme::HighContrast => Color::BLACK,
+                        _ => contrast(
+                            background.r * 255.0,
+                            background.g * 255.0,
+                            background.b * 255.0,
+                        ),
+                    },
+                    ..Default::default()
+                }
+            }
             ModernButton::Secondary => button::Appearance {
                 background: self.palette().buttons.secondary().into(),
-                border_radius: 100.0,
-                border_width: Self::BORDER_WIDTH,
-                border_color: Color::TRANSPARENT,
+                border_radius: self.palette().radii.button,
+                border_width: self.outline(),
+                border_color: self.palette().inputs.border_color(),
                 text_color: self.palette().buttons.label(),
                 ..Default::default()
             },
-            ModernButton::Tag((r, g, b)) => button::Appearance {
-                background: Self::from_rgb(*r, *g, *b).into(),
-                border_radius: 100.0,
+            ModernButton::Tag(engine) => button::Appearance {
+                background: engine
+                    .map_or(self.palette().buttons.tag(), |engine| {
+                        self.palette().engines.color(engine)
+                    })
+                    .into(),
+                border_radius: self.palette().radii.button,
                 border_width: 0.0,
                 border_color: Color::TRANSPARENT,
                 text_color: self.palette().buttons.label(),
                 ..Default::default()
             },
+            ModernButton::SelectedTag(engine) => button::Appearance {
+                border_width: 2.0,
+                border_color: accent().unwrap_or_else(|| self.palette().buttons.label()),
+                ..self.active(&ModernButton::Tag(Some(*engine)))
+            },
             ModernButton::Text => button::Appearance {
                 background: Color::TRANSPARENT.into(),
-                border_radius: 100.0,
+                border_radius: self.palette().radii.button,
                 text_color: self.palette().buttons.label(),
                 ..Default::default()
             },
+            ModernButton::Reveal => button::Appearance {
+                background: Color::TRANSPARENT.into(),
+                border_radius: self.palette().radii.button,
+                text_color: Color::TRANSPARENT,
+                ..Default::default()
+            },
+            ModernButton::Danger => {
+                let danger = self.palette().buttons.danger();
+                button::Appearance {
+                    background: Color { a: 0.15, ..danger }.into(),
+                    border_radius: self.palette().radii.button,
+                    border_width: 1.0,
+                    border_color: danger,
+                    text_color: danger,
+                    ..Default::default()
+                }
+            }
         }
     }
 
     fn hovered(&self, style: &Self::Style) -> button::Appearance {
         match style {
             ModernButton::Secondary => self.active(&ModernButton::Principal),
+            ModernButton::Reveal => button::Appearance {
+                text_color: Self::from_rgb(160.0, 160.0, 160.0),
+                ..self.active(style)
+            },
+            ModernButton::Danger => {
+                let danger = self.palette().buttons.danger();
+                button::Appearance {
+                    background: danger.into(),
+                    text_color: contrast(danger.r * 255.0, danger.g * 255.0, danger.b * 255.0),
+                    ..self.active(style)
+                }
+            }
             _ => self.active(style),
         }
     }
@@ -477,6 +1235,7 @@ impl button::StyleSheet for ModernTheme {
     fn pressed(&self, style: &Self::Style) -> button::Appearance {
         let active = match style {
             ModernButton::Secondary => self.active(&ModernButton::Principal),
+            ModernButton::Reveal | ModernButton::Danger => self.hovered(style),
             _ => self.active(style),
         };
 
@@ -484,16 +1243,16 @@ impl button::StyleSheet for ModernTheme {
             shadow_offset: Vector::default(),
             background: active.background.map(|background| match background {
                 Background::Color(color) => Background::Color(Color {
-                    r: color.r * 0.7,
-                    g: color.g * 0.7,
-                    b: color.b * 0.7,
+                    r: color.r * Self::PRESSED_DARKEN,
+                    g: color.g * Self::PRESSED_DARKEN,
+                    b: color.b * Self::PRESSED_DARKEN,
                     ..color
                 }),
             }),
             text_color: Color {
-                r: active.text_color.r * 0.7,
-                g: active.text_color.g * 0.7,
-                b: active.text_color.b * 0.7,
+                r: active.text_color.r * Self::PRESSED_DARKEN,
+                g: active.text_color.g * Self::PRESSED_DARKEN,
+                b: active.text_color.b * Self::PRESSED_DARKEN,
                 ..active.text_color
             },
             ..active
@@ -509,25 +1268,110 @@ impl container::StyleSheet for ModernTheme {
             ModernContainer::Default => container::Appearance::default(),
             ModernContainer::Input => container::Appearance {
                 background: self.palette().inputs.background().into(),
-                border_radius: 100.0,
-                border_width: 0.0,
-                border_color: Color::TRANSPARENT,
+                border_radius: self.palette().radii.input,
+                border_width: self.palette().container.border_width(),
+                border_color: self
+                    .palette()
+                    .container
+                    .border_color()
+                    .unwrap_or(Color::TRANSPARENT),
                 ..Default::default()
             },
+            ModernContainer::InputError => container::Appearance {
+                border_width: self.palette().container.border_width().max(1.5),
+                border_color: self.palette().buttons.danger(),
+                ..self.appearance(&ModernContainer::Input)
+            },
             ModernContainer::Historial => container::Appearance {
                 background: self.palette().inputs.background().into(),
-                border_radius: 35.0,
-                border_width: 0.0,
-                border_color: Color::TRANSPARENT,
+                border_radius: self.palette().container.border_radius(),
+                border_width: self.palette().container.border_width(),
+                border_color: self
+                    .palette()
+                    .container
+                    .border_color()
+                    .unwrap_or(Color::TRANSPARENT),
                 ..Default::default()
             },
             ModernContainer::Line => container::Appearance {
                 background: self.palette().inputs.placeholder_text().into(),
-                border_radius: 35.0,
+                border_radius: self.palette().container.border_radius(),
+                border_width: 0.0,
+                border_color: Color::TRANSPARENT,
+                ..Default::default()
+            },
+            ModernContainer::Badge(color) => container::Appearance {
+                background: match color {
+                    Some((r, g, b)) => Self::from_rgb(*r, *g, *b).into(),
+                    None => self.palette().buttons.secondary().into(),
+                },
+                border_radius: 100.0,
+                border_width: 0.0,
+                border_color: Color::TRANSPARENT,
+                ..Default::default()
+            },
+            ModernContainer::Avatar((r, g, b)) => container::Appearance {
+                text_color: Some(contrast(*r, *g, *b)),
+                background: Self::from_rgb(*r, *g, *b).into(),
+                border_radius: 100.0,
                 border_width: 0.0,
                 border_color: Color::TRANSPARENT,
+            },
+            ModernContainer::Popover => container::Appearance {
+                text_color: Some(self.palette().app.text()),
+                background: self.palette().inputs.background().into(),
+                border_radius: self.palette().radii.popover,
+                border_width: 1.0,
+                border_color: self.palette().inputs.border_color(),
+            },
+            ModernContainer::Backdrop => container::Appearance {
+                background: Color {
+                    a: 0.75,
+                    ..self.palette().app.background()
+                }
+                .into(),
+                ..Default::default()
+            },
+            ModernContainer::Modal => container::Appearance {
+                text_color: Some(self.palette().app.text()),
+                background: self.palette().inputs.background().into(),
+                border_radius: self.palette().radii.modal,
+                border_width: 1.0,
+                border_color: self.palette().inputs.border_color(),
+            },
+            ModernContainer::Tooltip => container::Appearance {
+                text_color: Some(Self::from_rgba(240.0, 240.0, 245.0, 100.0)),
+                background: Self::from_rgba(28.0, 28.0, 34.0, 95.0).into(),
+                border_radius: self.palette().radii.tooltip,
+                border_width: 1.0,
+                border_color: match self {
+                    ModernTheme::Light => Self::from_rgba(0.0, 0.0, 0.0, 20.0),
+                    ModernTheme::HighContrast => Color::WHITE,
+                    _ => Self::from_rgba(255.0, 255.0, 255.0, 15.0),
+                },
+            },
+            ModernContainer::Highlight => container::Appearance {
+                background: Color {
+                    a: 0.08,
+                    ..self.palette().app.text()
+                }
+                .into(),
+                border_radius: self.palette().radii.button,
+                border_width: 1.0,
+                border_color: accent().unwrap_or(self.palette().inputs.placeholder_text()),
                 ..Default::default()
             },
+            ModernContainer::Card => container::Appearance {
+                text_color: Some(self.palette().app.text()),
+                background: self.palette().inputs.background().into(),
+                border_radius: self.palette().container.border_radius(),
+                border_width: self.palette().container.border_width().max(1.0),
+                border_color: self
+                    .palette()
+                    .container
+                    .border_color()
+                    .unwrap_or(self.palette().inputs.border_color()),
+            },
         }
     }
 }
@@ -554,14 +1398,41 @@ impl toggler::StyleSheet for ModernTheme {
     }
 }
 
+impl radio::StyleSheet for ModernTheme {
+    type Style = ();
+
+    /// The selected radio gets an accent dot and ring, the rest a muted ring.
+    fn active(&self, _style: &Self::Style, is_selected: bool) -> radio::Appearance {
+        let palette = self.palette();
+        radio::Appearance {
+            background: palette.inputs.background().into(),
+            dot_color: palette.buttons.primary(),
+            border_width: 1.5,
+            border_color: if is_selected {
+                palette.buttons.primary()
+            } else {
+                palette.inputs.border_color()
+            },
+            text_color: None,
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style, is_selected: bool) -> radio::Appearance {
+        radio::Appearance {
+            border_color: self.palette().buttons.primary(),
+            ..self.active(style, is_selected)
+        }
+    }
+}
+
 impl text_input::StyleSheet for ModernTheme {
     type Style = ();
 
     fn active(&self, _style: &Self::Style) -> text_input::Appearance {
         text_input::Appearance {
             background: self.palette().inputs.background().into(),
-            border_radius: 100.0,
-            border_width: 0.0,
+            border_radius: self.palette().radii.input,
+            border_width: self.outline(),
             border_color: self.palette().inputs.border_color(),
             icon_color: self.palette().inputs.icon_color(),
         }
@@ -570,15 +1441,15 @@ impl text_input::StyleSheet for ModernTheme {
     fn focused(&self, _style: &Self::Style) -> text_input::Appearance {
         text_input::Appearance {
             background: self.palette().inputs.background().into(),
-            border_radius: 100.0,
-            border_width: 0.0,
+            border_radius: self.palette().radii.input,
+            border_width: self.outline(),
             border_color: self.palette().inputs.border_color(),
             icon_color: self.palette().inputs.icon_color(),
         }
     }
 
     fn placeholder_color(&self, _style: &Self::Style) -> Color {
-        self.palette().inputs.placeholder_text()
+        self.palette().inputs.placeholder()
     }
 
     fn value_color(&self, _style: &Self::Style) -> Color {
@@ -596,7 +1467,7 @@ impl text_input::StyleSheet for ModernTheme {
     fn disabled(&self, _style: &Self::Style) -> text_input::Appearance {
         text_input::Appearance {
             background: self.palette().inputs.background().into(),
-            border_radius: Self::BORDER_RADIUS,
+            border_radius: self.palette().radii.input,
             border_width: 2.0,
             border_color: self.palette().inputs.border_color(),
             icon_color: self.palette().inputs.icon_color(),
@@ -622,12 +1493,12 @@ impl scrollable::StyleSheet for ModernTheme {
 
     fn active(&self, _style: &Self::Style) -> scrollable::Scrollbar {
         scrollable::Scrollbar {
-            background: self.palette().buttons.secondary().into(),
+            background: self.palette().scrollbar.track.into(),
             border_radius: 90.0,
             border_width: 2.0,
             border_color: Color::TRANSPARENT,
             scroller: scrollable::Scroller {
-                color: self.palette().inputs.placeholder_text(),
+                color: self.palette().scrollbar.thumb,
                 border_radius: 90.0,
                 border_width: 2.0,
                 border_color: Color::TRANSPARENT,
@@ -641,16 +1512,50 @@ impl scrollable::StyleSheet for ModernTheme {
         _is_mouse_over_scrollbar: bool,
     ) -> scrollable::Scrollbar {
         scrollable::Scrollbar {
-            background: self.palette().inputs.placeholder_text().into(),
+            background: self.palette().scrollbar.hovered_track.into(),
             border_radius: 90.0,
             border_width: 2.0,
             border_color: Color::TRANSPARENT,
             scroller: scrollable::Scroller {
-                color: self.palette().buttons.primary(),
+                color: self.palette().scrollbar.hovered_thumb,
                 border_radius: 90.0,
                 border_width: 2.0,
                 border_color: Color::TRANSPARENT,
             },
         }
     }
+
+    /// Horizontal scrollbars are thin and have no track, they sit under rows of tags.
+    fn active_horizontal(&self, _style: &Self::Style) -> scrollable::Scrollbar {
+        scrollable::Scrollbar {
+            background: None,
+            border_radius: 90.0,
+            border_width: 0.0,
+            border_color: Color::TRANSPARENT,
+            scroller: scrollable::Scroller {
+                color: self.palette().scrollbar.thumb,
+                border_radius: 90.0,
+                border_width: 0.0,
+                border_color: Color::TRANSPARENT,
+            },
+        }
+    }
+
+    fn hovered_horizontal(
+        &self,
+        style: &Self::Style,
+        is_mouse_over_scrollbar: bool,
+    ) -> scrollable::Scrollbar {
+        let active = self.active_horizontal(style);
+        if !is_mouse_over_scrollbar {
+            return active;
+        }
+        scrollable::Scrollbar {
+            scroller: scrollable::Scroller {
+                color: self.palette().scrollbar.hovered_thumb,
+                ..active.scroller
+            },
+            ..active
+        }
+    }
 }
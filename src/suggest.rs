@@ -0,0 +1,91 @@
+use crate::engines::SearchEngine;
+
+/// Highest edit distance accepted to consider a bang a typo of an engine.
+pub const MAX_BANG_DISTANCE: usize = 3;
+
+/// Number of single character edits needed to turn `a` into `b`.
+///
+/// # Examples
+///
+/// ```
+/// use search::suggest::levenshtein;
+///
+/// assert_eq!(levenshtein("kitten", "sitting"), 3);
+/// assert_eq!(levenshtein("rust", "rust"), 0);
+/// assert_eq!(levenshtein("", "abc"), 3);
+/// ```
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Finds the engine alias closest to a mistyped bang.
+///
+/// Returns `None` when the bang already is a known alias or nothing is close enough.
+///
+/// # Examples
+///
+/// ```
+/// use search::engines::SearchEngine;
+/// use search::suggest::closest_bang;
+///
+/// assert_eq!(closest_bang("stakoverflow"), Some((SearchEngine::StackOverflow, "stackoverflow")));
+/// assert_eq!(closest_bang("stackoverflw"), Some((SearchEngine::StackOverflow, "stackoverflow")));
+/// assert_eq!(closest_bang("StackExchnge"), Some((SearchEngine::StackExchange, "stackexchange")));
+/// assert_eq!(closest_bang("stackoverflow"), None);
+/// assert_eq!(closest_bang("javascript"), None);
+/// ```
+pub fn closest_bang(bang: &str) -> Option<(SearchEngine, &'static str)> {
+    let bang = bang.to_lowercase();
+    if SearchEngine::from_alias(&bang).is_some() {
+        return None;
+    }
+    SearchEngine::KNOWN
+        .into_iter()
+        .flat_map(|engine| engine.aliases().iter().map(move |alias| (engine, *alias)))
+        // Short aliases are too easy to reach by accident.
+        .filter(|(_, alias)| alias.len() > 2)
+        .map(|(engine, alias)| (levenshtein(&bang, alias), engine, alias))
+        .filter(|(distance, _, _)| *distance <= MAX_BANG_DISTANCE)
+        .min_by_key(|(distance, _, _)| *distance)
+        .map(|(_, engine, alias)| (engine, alias))
+}
+
+/// Recommends a broader engine when `query` has more than `max_words` words, since
+/// long queries rarely match on narrow sites.
+///
+/// # Examples
+///
+/// ```
+/// use search::engines::SearchEngine;
+/// use search::suggest::broader_engine;
+///
+/// let long = "why does the borrow checker complain when i move a value into a closure";
+///
+/// assert_eq!(
+///     broader_engine(long, Some(SearchEngine::StackOverflow), 10),
+///     Some(SearchEngine::StackExchange)
+/// );
+/// assert_eq!(broader_engine("borrow checker", Some(SearchEngine::StackOverflow), 10), None);
+/// assert_eq!(broader_engine(long, Some(SearchEngine::StackExchange), 10), None);
+/// assert_eq!(broader_engine(long, None, 10), None);
+/// ```
+pub fn broader_engine(
+    query: &str,
+    engine: Option<SearchEngine>,
+    max_words: usize,
+) -> Option<SearchEngine> {
+    if query.split_whitespace().count() <= max_words {
+        return None;
+    }
+    engine?.broader()
+}
@@ -0,0 +1,156 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Keywords followed by the name of a symbol, across the usual languages.
+const KEYWORDS: [&str; 12] = [
+    "fn",
+    "struct",
+    "enum",
+    "trait",
+    "type",
+    "mod",
+    "class",
+    "def",
+    "func",
+    "function",
+    "interface",
+    "macro_rules!",
+];
+
+/// Source files that are worth indexing.
+const EXTENSIONS: [&str; 12] = [
+    "rs", "py", "js", "ts", "tsx", "go", "c", "h", "cpp", "hpp", "java", "kt",
+];
+
+/// Folders that only contain generated or third party code.
+const IGNORED_DIRS: [&str; 5] = ["target", "node_modules", ".git", "build", "dist"];
+
+/// [`SymbolIndex`] holds the symbol names found on a project directory.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolIndex {
+    symbols: BTreeSet<String>,
+}
+
+impl SymbolIndex {
+    /// Walks `root` indexing every source file found.
+    ///
+    /// Unreadable files and folders are skipped, and so are symlinks since they can
+    /// point back to a parent folder. This does blocking IO, so it must run outside
+    /// of the UI thread.
+    pub fn build(root: &Path) -> Self {
+        let mut index = Self::default();
+        let mut pending: Vec<PathBuf> = vec![root.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let kind = match entry.file_type() {
+                    Ok(kind) if !kind.is_symlink() => kind,
+                    _ => continue,
+                };
+                let path = entry.path();
+                let name = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or("");
+                if kind.is_dir() {
+                    if !IGNORED_DIRS.contains(&name) {
+                        pending.push(path);
+                    }
+                } else if path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| EXTENSIONS.contains(&ext))
+                {
+                    if let Ok(source) = fs::read_to_string(&path) {
+                        index.symbols.extend(extract(&source));
+                    }
+                }
+            }
+        }
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Returns up to `limit` symbols starting with `prefix`, ignoring case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use search::symbols::SymbolIndex;
+    ///
+    /// let index = SymbolIndex::from_source("struct AppState; fn apply_theme() {}");
+    ///
+    /// assert_eq!(index.complete("app", 5), vec!["AppState", "apply_theme"]);
+    /// assert!(index.complete("", 5).is_empty());
+    /// ```
+    pub fn complete(&self, prefix: &str, limit: usize) -> Vec<&str> {
+        if prefix.is_empty() {
+            return Vec::new();
+        }
+        let prefix = prefix.to_lowercase();
+        self.symbols
+            .iter()
+            .filter(|symbol| {
+                let symbol = symbol.to_lowercase();
+                symbol.starts_with(&prefix) && symbol != prefix
+            })
+            .take(limit)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Builds an index from a single source string.
+    pub fn from_source(source: &str) -> Self {
+        Self {
+            symbols: extract(source).collect(),
+        }
+    }
+}
+
+/// Extracts the names declared on the source through a naive tokenizer.
+///
+/// # Examples
+///
+/// ```
+/// use search::symbols::extract;
+///
+/// let source = "pub fn open_query(q: &str) {}\nclass Parser:\n    def parse(self): pass";
+///
+/// assert_eq!(
+///     extract(source).collect::<Vec<_>>(),
+///     vec!["open_query", "Parser", "parse"]
+/// );
+/// ```
+pub fn extract(source: &str) -> impl Iterator<Item = String> + '_ {
+    let mut tokens = source
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '!'))
+        .filter(|token| !token.is_empty());
+    std::iter::from_fn(move || loop {
+        let token = tokens.next()?;
+        if KEYWORDS.contains(&token) {
+            match tokens.next() {
+                Some(name) if is_identifier(name) => return Some(name.to_string()),
+                _ => continue,
+            }
+        }
+    })
+}
+
+fn is_identifier(token: &str) -> bool {
+    let mut chars = token.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+        && token.len() > 1
+}
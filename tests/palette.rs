@@ -0,0 +1,47 @@
+//! Exact colors given by the `PaletteConversor` conversions.
+
+use iced::Color;
+use search::styles::modern::PaletteConversor;
+
+struct Palette;
+
+impl PaletteConversor for Palette {}
+
+#[test]
+fn rgb_channels_scale_to_one() {
+    assert_eq!(Palette::from_rgb(0.0, 0.0, 0.0), Color::BLACK);
+    assert_eq!(Palette::from_rgb(255.0, 255.0, 255.0), Color::WHITE);
+    assert_eq!(
+        Palette::from_rgb(51.0, 102.0, 204.0),
+        Color::from_rgb(0.2, 0.4, 0.8)
+    );
+}
+
+#[test]
+fn alpha_scales_from_percent() {
+    assert_eq!(
+        Palette::from_rgba(127.0, 0.0, 255.0, 50.0),
+        Color {
+            r: 127.0 / 255.0,
+            g: 0.0,
+            b: 1.0,
+            a: 0.5,
+        }
+    );
+    assert_eq!(
+        Palette::from_rgba(255.0, 255.0, 255.0, 0.0),
+        Color::from_rgba(1.0, 1.0, 1.0, 0.0)
+    );
+    assert_eq!(
+        Palette::from_rgba(255.0, 0.0, 0.0, 100.0),
+        Palette::from_rgb(255.0, 0.0, 0.0)
+    );
+}
+
+#[test]
+fn out_of_range_values_are_clamped() {
+    assert_eq!(
+        Palette::from_rgba(-1.0, 256.0, 1000.0, 250.0),
+        Color::from_rgba(0.0, 1.0, 1.0, 1.0)
+    );
+}
@@ -0,0 +1,49 @@
+//! Drives the app through a whole session without opening a window.
+//!
+//! The app is headless, so searches are recorded on `opened_urls` instead of
+//! launching the browser.
+
+use iced::Application;
+use search::{
+    app::{App, Message},
+    engines::SearchEngine,
+    styles::modern::ModernTheme,
+};
+
+fn type_query(app: &mut App, query: &str) {
+    let _ = app.update(Message::QueryChange(query.into()));
+}
+
+#[test]
+fn search_session() {
+    let mut app = App::headless();
+    let _ = app.update(Message::HistoryLoaded(Ok(Vec::new())));
+
+    type_query(&mut app, "rust lifetimes");
+    let _ = app.update(Message::TagSelected(SearchEngine::StackOverflow));
+    let _ = app.update(Message::OnPressing);
+    assert_eq!(
+        app.opened_urls,
+        vec!["https://stackoverflow.com/search?q=rust%20lifetimes"]
+    );
+
+    let _ = app.update(Message::NextTheme);
+    assert_eq!(app.theme, ModernTheme::Light);
+
+    type_query(&mut app, "iced widgets");
+    let _ = app.update(Message::TagSelected(SearchEngine::StackOverflow));
+    let _ = app.update(Message::OnPressing);
+    // Deselecting the tag searches every engine.
+    assert_eq!(app.opened_urls.len(), 1 + SearchEngine::ALL.len());
+    let queries: Vec<&str> = app.searches.iter().map(|e| e.query.as_str()).collect();
+    assert_eq!(queries, vec!["iced widgets", "rust lifetimes"]);
+
+    let _ = app.update(Message::RemoveSearch(app.searches[1].id));
+    assert_eq!(app.searches.len(), 1);
+    assert_eq!(app.toast.as_deref(), Some("Search removed"));
+    let _ = app.update(Message::DismissToast);
+
+    let _ = app.update(Message::ClearHistory);
+    assert!(app.searches.is_empty());
+    assert_eq!(app.toast, None);
+}